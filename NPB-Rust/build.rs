@@ -0,0 +1,71 @@
+// Regenerates the NPB binaries under `src/bin` whenever a template or a
+// class parameter table changes, so `cargo build` always reflects the
+// current templates instead of requiring a manual `setparams` run first.
+//
+// We shell out to the `setparams` binary itself rather than duplicating its
+// generation logic here, since build.rs cannot depend on another binary
+// target of the same package as a library.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/templates");
+    println!("cargo:rerun-if-changed=src/params");
+
+    // (kernel, class) pairs for the binaries that are checked in under
+    // src/bin. "pp" kernels ignore the class argument but setparams still
+    // expects one, so we pass a placeholder.
+    //
+    // cg-pp-b is deliberately absent here: it hardcodes CLASS = "b" and has
+    // no src/templates/cg-pp.rs to regenerate from (it's maintained by hand,
+    // same as verify_all.rs treats it), so running setparams on it would
+    // just panic on a missing template file on every build.
+    let targets: &[(&str, &str)] = &[
+        ("ep", "s"),
+        ("ep-pp", "s"),
+        ("cg", "b"),
+        ("is", "s"),
+    ];
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+
+    for (kernel, class) in targets {
+        let output_path = Path::new(&manifest_dir).join("src/bin").join(format!(
+            "{}.rs",
+            if kernel.ends_with("-pp") || *kernel == "is" { kernel.to_string() } else { format!("{}-{}", kernel, class) }
+        ));
+        if !output_path.exists() {
+            // Don't generate binaries that weren't already checked in; this
+            // step only keeps existing ones in sync with their templates.
+            continue;
+        }
+
+        let status = Command::new(env!("CARGO"))
+            .current_dir(&manifest_dir)
+            .args(["run", "--quiet", "--bin", "setparams", "--", kernel, class])
+            .status();
+
+        match status {
+            Ok(s) if s.success() => {}
+            Ok(s) => println!(
+                "cargo:warning=setparams exited with {} while regenerating {} {}",
+                s, kernel, class
+            ),
+            Err(e) => println!(
+                "cargo:warning=failed to run setparams for {} {}: {}",
+                kernel, class, e
+            ),
+        }
+    }
+
+    // Record when the generator last ran. Nothing reads this marker back:
+    // the rerun-if-changed lines above decide when this script runs, and
+    // the timestamp is only there for CI logs.
+    let _ = fs::write(
+        Path::new(&manifest_dir).join("target").join(".setparams-last-run"),
+        chrono::Local::now().to_rfc3339(),
+    );
+}
@@ -1,30 +1,274 @@
+use std::sync::atomic::{AtomicI64, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Mutex;
 
 use crate::pega_tempo;
 
-static START: Mutex<[f64; 64]> = Mutex::new([0.0; 64]);
-static ELAPSED: Mutex<[f64; 64]> = Mutex::new([0.0; 64]);
-pub fn timer_clear(x:usize){
-    let mut elapsed = ELAPSED.lock().unwrap();
-    elapsed[x] = 0.0;
+const NUM_SLOTS: usize = 64;
+const ZERO: AtomicU64 = AtomicU64::new(0);
+const ZERO_I64: AtomicI64 = AtomicI64::new(0);
+const ZERO_USIZE: AtomicUsize = AtomicUsize::new(0);
+const EMPTY_START_STACK: Mutex<Vec<f64>> = Mutex::new(Vec::new());
+
+static ELAPSED: [AtomicU64; NUM_SLOTS] = [ZERO; NUM_SLOTS];
+
+/// Per-slot stack of pending `timer_start` readings, so a region entered
+/// recursively (e.g. multigrid's `MG`/`CG` calling itself) doesn't have its
+/// outer start overwritten by an inner one. `timer_start` pushes, and only
+/// the pop that brings `DEPTH` back to zero accumulates into `ELAPSED`.
+///
+/// This one slot-local `Mutex` is the only lock left on the hot path: the
+/// cross-slot contention that originally motivated moving to atomics is
+/// gone (each slot has its own lock), but `timer_start`/`timer_stop` still
+/// take it, so this module is "per-slot lock-free" rather than lock-free.
+/// `ELAPSED`/`DEPTH`/`RSS_START`/`RSS_DELTA` are the parts that are
+/// genuinely lock-free, via the atomics/CAS loops below.
+static START_STACK: [Mutex<Vec<f64>>; NUM_SLOTS] = [EMPTY_START_STACK; NUM_SLOTS];
+/// Re-entrancy depth for each slot; exposed via `timer_depth` for debugging
+/// unbalanced `timer_start`/`timer_stop` pairs.
+static DEPTH: [AtomicUsize; NUM_SLOTS] = [ZERO_USIZE; NUM_SLOTS];
+
+/// Resident-set-size in bytes at the most recent `timer_start`/`timer_stop`
+/// (or `timer_span`) for each slot, used to compute `RSS_DELTA`.
+static RSS_START: [AtomicI64; NUM_SLOTS] = [ZERO_I64; NUM_SLOTS];
+/// Resident-set-size growth (bytes) over the most recent timed region for
+/// each slot -- can be negative if memory was freed during the region.
+static RSS_DELTA: [AtomicI64; NUM_SLOTS] = [ZERO_I64; NUM_SLOTS];
+
+/// Optional human-readable label for each slot, set via `timer_name` and
+/// used by `timer_report` so a summary isn't just a row of bare indices.
+static NAMES: Mutex<[Option<&'static str>; NUM_SLOTS]> = Mutex::new([None; NUM_SLOTS]);
+
+/// Clock reading used to clamp `wtime()` against: an NTP step or a
+/// suspend/resume can make the OS clock jump backwards, which would
+/// otherwise turn `now - start` negative and corrupt a slot's
+/// accumulated elapsed time forever.
+static LAST_NOW: AtomicU64 = AtomicU64::new(0);
+
+/// Reads `pega_tempo::wtime()` and clamps it to be no earlier than any
+/// previously observed reading, via a CAS loop so concurrent callers
+/// never see the clock run backwards.
+fn monotonic_now() -> f64 {
+    let now = pega_tempo::wtime().to_bits();
+    let mut last = LAST_NOW.load(Ordering::Relaxed);
+    loop {
+        let clamped = now.max(last);
+        match LAST_NOW.compare_exchange_weak(last, clamped, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => return f64::from_bits(clamped),
+            Err(observed) => last = observed,
+        }
+    }
 }
-pub fn timer_start(x:usize){
-    let mut start = START.lock().unwrap();
-    start[x] = pega_tempo::wtime();
+
+pub fn timer_clear(x: usize) {
+    ELAPSED[x].store(0.0f64.to_bits(), Ordering::Relaxed);
 }
-pub fn timer_stop(x:usize){
-    let start_time;
-    {
-        let start = START.lock().unwrap();
-        start_time = start[x];
+
+/// Pushes the current time onto slot `x`'s start stack and increments its
+/// depth, so a nested/re-entrant call into the same region doesn't clobber
+/// the outer call's start time.
+pub fn timer_start(x: usize) {
+    let now = monotonic_now();
+    START_STACK[x].lock().unwrap().push(now);
+    if DEPTH[x].fetch_add(1, Ordering::Relaxed) == 0 {
+        RSS_START[x].store(current_rss_bytes(), Ordering::Relaxed);
     }
-    let now:f64 = pega_tempo::wtime();
+}
+
+/// Pops slot `x`'s start stack; only accumulates elapsed time and RSS delta
+/// once depth returns to zero, so an outer region's total wall time isn't
+/// shortened by inner re-entrant calls. A slot with nothing left to pop
+/// (depth already zero) is a no-op rather than subtracting a stale start.
+pub fn timer_stop(x: usize) {
+    let start_time = match START_STACK[x].lock().unwrap().pop() {
+        Some(t) => t,
+        None => return,
+    };
+    let depth_after = DEPTH[x].fetch_sub(1, Ordering::Relaxed) - 1;
+    if depth_after != 0 {
+        return;
+    }
+
+    let now = monotonic_now();
     let elapse = now - start_time;
-    let mut elapsed = ELAPSED.lock().unwrap();
-    elapsed[x] += elapse;
+    add_elapsed(x, elapse);
+
+    let rss_start = RSS_START[x].load(Ordering::Relaxed);
+    RSS_DELTA[x].store(current_rss_bytes() - rss_start, Ordering::Relaxed);
+}
+
+/// Current re-entrancy depth of slot `x` -- `0` once every `timer_start`
+/// has a matching `timer_stop`; a non-zero value after a benchmark run
+/// flags an unbalanced pair.
+pub fn timer_depth(x: usize) -> usize {
+    DEPTH[x].load(Ordering::Relaxed)
+}
+
+/// Bytes of resident memory currently used by this process, or `0` if the
+/// platform query fails -- a failed memory read never affects the time
+/// path above, it just leaves that slot's RSS delta at zero.
+#[cfg(target_os = "linux")]
+fn current_rss_bytes() -> i64 {
+    std::fs::read_to_string("/proc/self/statm")
+        .ok()
+        .and_then(|contents| contents.split_whitespace().nth(1)?.parse::<i64>().ok())
+        .map(|resident_pages| resident_pages * 4096)
+        .unwrap_or(0)
+}
+
+/// On macOS this reports `ru_maxrss`, the peak resident size so far, not
+/// the current one.
+#[cfg(target_os = "macos")]
+fn current_rss_bytes() -> i64 {
+    #[repr(C)]
+    struct Rusage {
+        ru_utime: [i64; 2],
+        ru_stime: [i64; 2],
+        ru_maxrss: i64,
+        _rest: [i64; 14],
+    }
+
+    extern "C" {
+        fn getrusage(who: i32, usage: *mut Rusage) -> i32;
+    }
+
+    const RUSAGE_SELF: i32 = 0;
+
+    unsafe {
+        let mut usage: Rusage = std::mem::zeroed();
+        if getrusage(RUSAGE_SELF, &mut usage) == 0 {
+            usage.ru_maxrss
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn current_rss_bytes() -> i64 {
+    #[repr(C)]
+    struct ProcessMemoryCounters {
+        cb: u32,
+        page_fault_count: u32,
+        peak_working_set_size: usize,
+        working_set_size: usize,
+        quota_peak_paged_pool_usage: usize,
+        quota_paged_pool_usage: usize,
+        quota_peak_non_paged_pool_usage: usize,
+        quota_non_paged_pool_usage: usize,
+        pagefile_usage: usize,
+        peak_pagefile_usage: usize,
+    }
+
+    extern "system" {
+        fn GetCurrentProcess() -> isize;
+        fn K32GetProcessMemoryInfo(process: isize, counters: *mut ProcessMemoryCounters, size: u32) -> i32;
+    }
+
+    unsafe {
+        let mut counters: ProcessMemoryCounters = std::mem::zeroed();
+        counters.cb = std::mem::size_of::<ProcessMemoryCounters>() as u32;
+        let process = GetCurrentProcess();
+        if K32GetProcessMemoryInfo(process, &mut counters, counters.cb) != 0 {
+            counters.working_set_size as i64
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn current_rss_bytes() -> i64 {
+    0
+}
+
+/// `f64` addition has no native atomic fetch-add, so accumulate via a
+/// CAS loop over the bit-cast representation instead.
+fn add_elapsed(x: usize, delta: f64) {
+    let mut current = ELAPSED[x].load(Ordering::Relaxed);
+    loop {
+        let updated = (f64::from_bits(current) + delta).to_bits();
+        match ELAPSED[x].compare_exchange_weak(current, updated, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => return,
+            Err(observed) => current = observed,
+        }
+    }
+}
+
+pub fn timer_read(x: usize) -> f64 {
+    f64::from_bits(ELAPSED[x].load(Ordering::Relaxed))
+}
+
+/// Resident-set-size growth, in bytes, over slot `x`'s most recently timed
+/// region. Negative if memory was freed during the region, `0` if the
+/// platform's memory query isn't supported or failed.
+pub fn timer_read_rss(x: usize) -> i64 {
+    RSS_DELTA[x].load(Ordering::Relaxed)
 }
 
-pub fn timer_read(x:usize) -> f64{
-    let elapsed = ELAPSED.lock().unwrap();
-    elapsed[x]
+/// Calls `timer_stop` on its slot when dropped, so the `timer_start`
+/// it's paired with can't be left unbalanced by an early return out of
+/// the timed region.
+struct TimerSpanGuard {
+    slot: usize,
+}
+
+impl Drop for TimerSpanGuard {
+    fn drop(&mut self) {
+        timer_stop(self.slot);
+    }
+}
+
+/// Runs `f`, timing it into slot `x` without requiring a manual
+/// `timer_start`/`timer_stop` pair. The stop happens in a guard's `Drop`,
+/// so it still fires even if `f` returns early, and nests correctly with
+/// `timer_start`/`timer_stop` on the same slot since it goes through them.
+pub fn timer_span<F, R>(x: usize, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    timer_start(x);
+    let _guard = TimerSpanGuard { slot: x };
+    f()
+}
+
+/// Attaches a label to slot `x` (e.g. `timer_name(T_CONJ_GRAD, "conj_grad")`)
+/// so `timer_report` can render a self-documenting summary instead of a row
+/// of anonymous indices.
+pub fn timer_name(x: usize, name: &'static str) {
+    NAMES.lock().unwrap()[x] = Some(name);
+}
+
+/// Formats a duration in seconds with adaptive units, picked by magnitude
+/// so small and large regions both read naturally (`1.250ms`, `3.400s`).
+fn format_duration(seconds: f64) -> String {
+    if seconds >= 1.0 {
+        format!("{:.3}s", seconds)
+    } else if seconds >= 1e-3 {
+        format!("{:.3}ms", seconds * 1e3)
+    } else if seconds >= 1e-6 {
+        format!("{:.3}us", seconds * 1e6)
+    } else {
+        format!("{:.3}ns", seconds * 1e9)
+    }
+}
+
+/// Renders every non-zero timer slot as a labeled summary line, each
+/// showing its elapsed time and its percentage of the largest timed
+/// region -- a ready-to-print block instead of a row of bare `f64`s that
+/// every NPB kernel would otherwise have to format itself.
+pub fn timer_report() -> String {
+    let names = NAMES.lock().unwrap();
+    let elapsed: Vec<f64> = (0..NUM_SLOTS).map(timer_read).collect();
+    let max_elapsed = elapsed.iter().cloned().fold(0.0_f64, f64::max);
+
+    let mut report = String::new();
+    for (x, &value) in elapsed.iter().enumerate() {
+        if value == 0.0 {
+            continue;
+        }
+        let label = names[x].unwrap_or("(unnamed)");
+        let pct = if max_elapsed > 0.0 { value / max_elapsed * 100.0 } else { 0.0 };
+        report.push_str(&format!("  [{:2}] {:<16} {:>10}  ({:5.1}% of largest)\n", x, label, format_duration(value), pct));
+    }
+    report
 }
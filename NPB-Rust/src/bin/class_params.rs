@@ -0,0 +1,64 @@
+// Per-class benchmark parameter tables, loaded from the CSV files under
+// `src/params/` instead of being hardcoded as `match` arms in setparams.rs.
+// Keeping the tables external means adding/tuning a class (or adding a new
+// kernel's table) doesn't require touching the generator's Rust code.
+
+use std::collections::HashMap;
+use std::fs;
+
+const PARAMS_PATH: &str = "./src/params";
+
+#[derive(Debug, Clone)]
+pub struct EpClassParams {
+    pub m: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct CgClassParams {
+    pub na: i64,
+    pub nonzer: i32,
+    pub niter: String,
+    pub shift: String,
+}
+
+pub fn load_ep_classes() -> HashMap<String, EpClassParams> {
+    let mut table = HashMap::new();
+    for row in read_rows("ep_classes.csv") {
+        if row.len() < 2 {
+            continue;
+        }
+        if let Ok(m) = row[1].parse::<u32>() {
+            table.insert(row[0].clone(), EpClassParams { m });
+        }
+    }
+    table
+}
+
+pub fn load_cg_classes() -> HashMap<String, CgClassParams> {
+    let mut table = HashMap::new();
+    for row in read_rows("cg_classes.csv") {
+        if row.len() < 5 {
+            continue;
+        }
+        let (Ok(na), Ok(nonzer)) = (row[1].parse::<i64>(), row[2].parse::<i32>()) else {
+            continue;
+        };
+        table.insert(
+            row[0].clone(),
+            CgClassParams { na, nonzer, niter: row[3].clone(), shift: row[4].clone() },
+        );
+    }
+    table
+}
+
+fn read_rows(file_name: &str) -> Vec<Vec<String>> {
+    let path = format!("{}/{}", PARAMS_PATH, file_name);
+    let contents = fs::read_to_string(&path).expect(&format!("Failed to read params file: {}", path));
+
+    contents
+        .lines()
+        .skip(1) // header
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.split(',').map(|field| field.trim().to_string()).collect())
+        .collect()
+}
@@ -1,8 +1,12 @@
-use std::env;
+mod class_params;
+mod template_engine;
+
 use std::fs;
 use std::fs::File;
 use std::io::Write as _;
 use chrono::Local;
+use clap::Parser;
+use template_engine::TemplateContext;
 
 const BIN_PATH: &str = "./src/bin";
 const TEMPLATE_PATH: &str = "./src/templates";
@@ -10,77 +14,166 @@ const CG_TEMPLATEPATH: &str = "./src/templates/cg.rs";
 const EP_TEMPLATEPATH: &str = "./src/templates/ep.rs";
 const IS_TEMPLATEPATH: &str = "./src/templates/is.rs";
 
+const KERNELS: &[&str] = &["ep", "ep-pp", "cg", "cg-pp", "is"];
+
+/// NPB Rust kernel source generator: expands the `%% PLACEHOLDER %%`
+/// templates under `src/templates` into compilable binaries under `src/bin`.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct Cli {
+    /// Kernel to generate (ep, ep-pp, cg, cg-pp, is). Omit with --all or --batch.
+    kernel: Option<String>,
+
+    /// NPB class (s, w, a, b, c, d, e). Omit with --all or --batch.
+    class: Option<String>,
+
+    /// Generate every kernel for every class in its parameter table.
+    #[arg(long)]
+    all: bool,
+
+    /// Read "<kernel> <class>" pairs from FILE, one per line, and generate each.
+    #[arg(long, value_name = "FILE")]
+    batch: Option<String>,
+}
+
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    if args.len() < 3 {
-        eprintln!("Usage: {} <kernel> <class>", args[0]);
-        eprintln!("Example: {} cg-pp s", args[0]);
-        std::process::exit(1);
+    let cli = Cli::parse();
+
+    if cli.all {
+        generate_all();
+        return;
+    }
+
+    if let Some(batch_file) = &cli.batch {
+        generate_batch(batch_file);
+        return;
     }
-    
-    let mut kernel = &args[1];
-    let mut class_npb = &args[2];
-    let binding = kernel.to_lowercase();
-    kernel = &binding;
-    let binding2 = class_npb.to_lowercase();
-    class_npb = &binding2;
-
-    if kernel == "ep" {
-        write_ep_info(class_npb.as_str());
-    } else if kernel == "ep-pp" {
-        write_ep_pp_info(class_npb.as_str());
-    } else if kernel == "cg" {
-        write_cg_info(class_npb.as_str());
-    } else if kernel == "cg-pp" {
-        write_cg_pp_info(class_npb.as_str());
-    } else if kernel == "is" {
-        write_is_info(class_npb.as_str());
-    } else {
-        eprintln!("Unknown kernel: {}", kernel);
-        eprintln!("Supported kernels: ep, ep-pp, cg, cg-pp, is");
+
+    let (Some(kernel), Some(class)) = (&cli.kernel, &cli.class) else {
+        eprintln!("Usage: setparams <kernel> <class>");
+        eprintln!("       setparams --all");
+        eprintln!("       setparams --batch <file>");
+        eprintln!("Example: setparams cg-pp s");
         std::process::exit(1);
+    };
+
+    generate(kernel, class);
+}
+
+fn generate_all() {
+    let ep_classes = class_params::load_ep_classes();
+    let cg_classes = class_params::load_cg_classes();
+
+    for class in ep_classes.keys() {
+        generate_if_template_exists("ep", class);
+    }
+    for class in cg_classes.keys() {
+        generate_if_template_exists("cg", class);
+    }
+    // The "-pp" kernels and IS pick their class at runtime, so generating
+    // them once (their generators ignore the class argument) is enough.
+    generate_if_template_exists("ep-pp", "s");
+    generate_if_template_exists("cg-pp", "s");
+    generate_if_template_exists("is", "s");
+}
+
+/// Template path `generate` would read for `kernel`, mirroring the match in
+/// `generate()` itself.
+fn template_path_for(kernel: &str) -> String {
+    match kernel {
+        "ep" => EP_TEMPLATEPATH.to_string(),
+        "ep-pp" | "is" => format!("{}/{}.rs", TEMPLATE_PATH, kernel),
+        "cg" => CG_TEMPLATEPATH.to_string(),
+        "cg-pp" => format!("{}/cg-pp.rs", TEMPLATE_PATH),
+        other => format!("{}/{}.rs", TEMPLATE_PATH, other),
+    }
+}
+
+/// Like `generate`, but skips with a warning instead of panicking when the
+/// kernel's template file hasn't landed yet, so `--all` covers every kernel
+/// that's actually ready rather than crashing on the first one that isn't.
+fn generate_if_template_exists(kernel: &str, class: &str) {
+    let template_path = template_path_for(kernel);
+    if !std::path::Path::new(&template_path).exists() {
+        eprintln!(
+            "Warning: skipping {} {} (template not found: {})",
+            kernel, class, template_path
+        );
+        return;
+    }
+    generate(kernel, class);
+}
+
+fn generate_batch(path: &str) {
+    let contents = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Failed to read batch file {}: {}", path, e));
+
+    for (line_no, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next()) {
+            (Some(kernel), Some(class)) => generate(kernel, class),
+            _ => eprintln!(
+                "Skipping malformed batch line {} in {}: {:?}",
+                line_no + 1,
+                path,
+                line
+            ),
+        }
+    }
+}
+
+fn generate(kernel: &str, class: &str) {
+    let kernel = kernel.to_lowercase();
+    let class = class.to_lowercase();
+
+    match kernel.as_str() {
+        "ep" => write_ep_info(&class),
+        "ep-pp" => write_ep_pp_info(&class),
+        "cg" => write_cg_info(&class),
+        "cg-pp" => write_cg_pp_info(&class),
+        "is" => write_is_info(&class),
+        other => {
+            eprintln!("Unknown kernel: {}", other);
+            eprintln!("Supported kernels: {}", KERNELS.join(", "));
+            std::process::exit(1);
+        }
     }
 }
 
 fn write_ep_info(class_npb: &str) {
-    let mut binding = fs::read_to_string(&EP_TEMPLATEPATH).expect("File");
-    let mut contents: &str = binding.as_mut_str();
-
-    let m: u32 = match class_npb {
-        "s"=>24,
-        "w"=>25,
-        "a"=>28,
-        "b"=>30,
-        "c"=>32,
-        "d"=>36,
-        "e"=>40,
-        _=>24
-    };
+    let template = fs::read_to_string(&EP_TEMPLATEPATH).expect("File");
+
+    let ep_classes = class_params::load_ep_classes();
+    let m: u32 = ep_classes.get(class_npb).map(|p| p.m).unwrap_or(24);
 
     let compile_time = Local::now().to_rfc3339();
 
-    binding = contents.replace("%% CLASS_NPB %%", format!("\"{}\"", class_npb).as_str());
-    contents = binding.as_mut_str();
-    binding = contents.replace("%% M %%", format!("{}", m).as_str());
-    contents = binding.as_mut_str();
-    binding = contents.replace("%% COMPILE_TIME %%", format!("\"{}\"", compile_time).as_str());
-    contents = binding.as_mut_str();
+    let mut ctx = TemplateContext::new();
+    ctx.set_str("CLASS_NPB", format!("\"{}\"", class_npb));
+    ctx.set_str("M", format!("{}", m));
+    ctx.set_str("COMPILE_TIME", format!("\"{}\"", compile_time));
+
+    let contents = template_engine::render(&template, &ctx);
 
     let mut bin_file = File::create(format!("{}/ep-{}.rs", &BIN_PATH, class_npb)).unwrap();
-    let _ = bin_file.write_all(&contents.as_bytes());
+    let _ = bin_file.write_all(contents.as_bytes());
 }
 
 fn write_ep_pp_info(class_npb: &str) {
     let template_file_path = format!("{}/ep-pp.rs", TEMPLATE_PATH);
-    let mut binding = fs::read_to_string(&template_file_path)
+    let template = fs::read_to_string(&template_file_path)
         .expect(&format!("Failed to read template file: {}", template_file_path));
-    let mut contents: &str = binding.as_mut_str();
 
     let compile_time = Local::now().to_rfc3339();
-    
-    binding = contents.replace("%% COMPILE_TIME %%", format!("\"{}\"", compile_time).as_str());
-    contents = binding.as_mut_str();
+
+    let mut ctx = TemplateContext::new();
+    ctx.set_str("COMPILE_TIME", format!("\"{}\"", compile_time));
+
+    let contents = template_engine::render(&template, &ctx);
 
     let output_bin_filename = format!("{}/ep-pp.rs", &BIN_PATH);
     match File::create(&output_bin_filename) {
@@ -101,79 +194,44 @@ fn write_ep_pp_info(class_npb: &str) {
 }
 
 fn write_cg_info(class_npb: &str) {
-    let mut binding = fs::read_to_string(&format!("{}/cg.rs", TEMPLATE_PATH)).expect("File");
-    let mut contents: &str = binding.as_mut_str();
-
-    let na = match class_npb {
-        "s" => 1400,
-        "w" => 7000,
-        "a" => 14000,
-        "b" => 75000,
-        "c" => 150000,
-        "d" => 1500000,
-        "e" => 9000000,
-        _   => 1400
-    };
-    let nonzer = match class_npb {
-        "s" => 7,
-        "w" => 8,
-        "a" => 11,
-        "b" => 13,
-        "c" => 15,
-        "d" => 21,
-        "e" => 26,
-        _   => 7
-    };
-    let niter = match class_npb {
-        "s" => "15",
-        "w" => "15",
-        "a" => "15",
-        "b" => "75",
-        "c" => "75",
-        "d" => "100",
-        "e" => "100",
-        _   => "15"
-    };
-    let shift = match class_npb {
-        "s" => "10.0",
-        "w" => "12.0",
-        "a" => "20.0",
-        "b" => "60.0",
-        "c" => "110.0",
-        "d" => "500.0",
-        "e" => "1500.0",
-        _   => "10.0"
+    let template = fs::read_to_string(&format!("{}/cg.rs", TEMPLATE_PATH)).expect("File");
+
+    let cg_classes = class_params::load_cg_classes();
+    let default_params = class_params::CgClassParams {
+        na: 1400,
+        nonzer: 7,
+        niter: "15".to_string(),
+        shift: "10.0".to_string(),
     };
+    let params = cg_classes.get(class_npb).unwrap_or(&default_params);
 
     let compile_time = Local::now().to_rfc3339();
 
-    binding = contents.replace("%% CLASS_NPB %%", format!("\"{}\"", class_npb).as_str());
-    contents = binding.as_mut_str();
-    binding = contents.replace("%% NA %%", format!("{}", na).as_str());
-    contents = binding.as_mut_str();
-    binding = contents.replace("%% NONZER %%", format!("{}", nonzer).as_str());
-    contents = binding.as_mut_str();
-    binding = contents.replace("%% NITER %%", niter);
-    contents = binding.as_mut_str();
-    binding = contents.replace("%% SHIFT %%", shift);
-    contents = binding.as_mut_str();
-    binding = contents.replace("%% COMPILE_TIME %%", format!("\"{}\"", compile_time).as_str());
-    contents = binding.as_mut_str();
+    let mut ctx = TemplateContext::new();
+    ctx.set_str("CLASS_NPB", format!("\"{}\"", class_npb));
+    ctx.set_str("NA", format!("{}", params.na));
+    ctx.set_str("NONZER", format!("{}", params.nonzer));
+    ctx.set_str("NITER", &params.niter);
+    ctx.set_str("SHIFT", &params.shift);
+    ctx.set_str("COMPILE_TIME", format!("\"{}\"", compile_time));
+
+    let contents = template_engine::render(&template, &ctx);
 
     let mut bin_file = File::create(format!("{}/cg-{}.rs", &BIN_PATH, class_npb)).unwrap();
-    let _ = bin_file.write_all(&contents.as_bytes());
+    let _ = bin_file.write_all(contents.as_bytes());
 }
 
 fn write_cg_pp_info(class_npb: &str) {
     let template_file_path = format!("{}/cg-pp.rs", TEMPLATE_PATH);
-    let mut binding = fs::read_to_string(&template_file_path)
+    let template = fs::read_to_string(&template_file_path)
         .expect(&format!("Failed to read template file: {}", template_file_path));
-    let mut contents: &str = binding.as_mut_str();
 
     let compile_time = Local::now().to_rfc3339();
-    
-    binding = contents.replace("%% COMPILE_TIME %%", format!("\"{}\"", compile_time).as_str());
-    contents = binding.as_mut_str();
+
+    let mut ctx = TemplateContext::new();
+    ctx.set_str("COMPILE_TIME", format!("\"{}\"", compile_time));
+
+    let contents = template_engine::render(&template, &ctx);
 
     let output_bin_filename = format!("{}/cg-pp.rs", &BIN_PATH);
     match File::create(&output_bin_filename) {
@@ -195,14 +253,15 @@ fn write_cg_pp_info(class_npb: &str) {
 
 fn write_is_info(class_npb: &str) {
     let template_file_path = format!("{}/is.rs", TEMPLATE_PATH);
-    let mut binding = fs::read_to_string(&template_file_path)
+    let template = fs::read_to_string(&template_file_path)
         .expect(&format!("Failed to read template file: {}", template_file_path));
-    let mut contents: &str = binding.as_mut_str();
 
     let compile_time = Local::now().to_rfc3339();
-    
-    binding = contents.replace("%% COMPILE_TIME %%", format!("\"{}\"", compile_time).as_str());
-    contents = binding.as_mut_str();
+
+    let mut ctx = TemplateContext::new();
+    ctx.set_str("COMPILE_TIME", format!("\"{}\"", compile_time));
+
+    let contents = template_engine::render(&template, &ctx);
 
     let output_bin_filename = format!("{}/is.rs", &BIN_PATH);
     match File::create(&output_bin_filename) {
@@ -0,0 +1,219 @@
+// Minimal template engine for the NPB code generator.
+//
+// Supports the existing `%% NAME %%` variable substitution plus two block
+// directives using the same `%%` delimiter so generated templates stay
+// visually consistent with the rest of the NPB sources:
+//
+//   %% IF NAME %% ... %% ENDIF %%
+//   %% FOR ITEM IN LIST %% ... %% ENDFOR %%
+//
+// Blocks may nest. Loop bodies see `%% ITEM.FIELD %%` for struct fields of
+// the current row.
+
+use std::collections::HashMap;
+
+#[derive(Clone, Debug)]
+pub enum Value {
+    Str(String),
+    Bool(bool),
+    List(Vec<HashMap<String, Value>>),
+}
+
+impl Value {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Bool(b) => *b,
+            Value::Str(s) => !s.is_empty(),
+            Value::List(l) => !l.is_empty(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TemplateContext {
+    vars: HashMap<String, Value>,
+}
+
+impl TemplateContext {
+    pub fn new() -> Self {
+        Self { vars: HashMap::new() }
+    }
+
+    pub fn set_str(&mut self, name: &str, value: impl Into<String>) -> &mut Self {
+        self.vars.insert(name.to_string(), Value::Str(value.into()));
+        self
+    }
+
+    pub fn set_bool(&mut self, name: &str, value: bool) -> &mut Self {
+        self.vars.insert(name.to_string(), Value::Bool(value));
+        self
+    }
+
+    pub fn set_list(&mut self, name: &str, rows: Vec<HashMap<String, Value>>) -> &mut Self {
+        self.vars.insert(name.to_string(), Value::List(rows));
+        self
+    }
+}
+
+/// Renders `template`, substituting variables and evaluating `IF`/`FOR`
+/// blocks against `ctx`. Unknown variables are left in the output as
+/// `%% NAME %%`, so a template can be rendered again in a later pass.
+pub fn render(template: &str, ctx: &TemplateContext) -> String {
+    render_scoped(template, &ctx.vars)
+}
+
+fn render_scoped(template: &str, scope: &HashMap<String, Value>) -> String {
+    let tokens = tokenize(template);
+    let mut out = String::with_capacity(template.len());
+    let mut i = 0;
+    render_block(&tokens, &mut i, scope, &mut out);
+    out
+}
+
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Var(String),
+    If(String),
+    EndIf,
+    For(String, String), // (item_name, list_name)
+    EndFor,
+}
+
+fn tokenize(template: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("%%") {
+        if start > 0 {
+            tokens.push(Token::Text(rest[..start].to_string()));
+        }
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("%%") else {
+            // Unterminated directive: treat the rest as literal text.
+            tokens.push(Token::Text(rest[start..].to_string()));
+            return tokens;
+        };
+        let directive = after_open[..end].trim();
+        tokens.push(classify(directive));
+        rest = &after_open[end + 2..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+    tokens
+}
+
+fn classify(directive: &str) -> Token {
+    if let Some(cond) = directive.strip_prefix("IF ") {
+        return Token::If(cond.trim().to_string());
+    }
+    if directive == "ENDIF" {
+        return Token::EndIf;
+    }
+    if let Some(rest) = directive.strip_prefix("FOR ") {
+        if let Some((item, list)) = rest.split_once(" IN ") {
+            return Token::For(item.trim().to_string(), list.trim().to_string());
+        }
+    }
+    if directive == "ENDFOR" {
+        return Token::EndFor;
+    }
+    Token::Var(directive.to_string())
+}
+
+/// Renders tokens starting at `*i` until a matching ENDIF/ENDFOR or EOF,
+/// leaving `*i` on the terminator it stopped at for the caller to consume.
+fn render_block(
+    tokens: &[Token],
+    i: &mut usize,
+    scope: &HashMap<String, Value>,
+    out: &mut String,
+) {
+    while *i < tokens.len() {
+        match &tokens[*i] {
+            Token::Text(s) => {
+                out.push_str(s);
+                *i += 1;
+            }
+            Token::Var(name) => {
+                if let Some(value) = resolve(scope, name) {
+                    if let Some(s) = value.as_str() {
+                        out.push_str(s);
+                    }
+                } else {
+                    // Leave unresolved placeholders untouched for later passes.
+                    out.push_str("%% ");
+                    out.push_str(name);
+                    out.push_str(" %%");
+                }
+                *i += 1;
+            }
+            Token::If(cond) => {
+                *i += 1;
+                let truthy = resolve(scope, cond).map(Value::is_truthy).unwrap_or(false);
+                let mut branch = String::new();
+                render_block(tokens, i, scope, &mut branch);
+                if truthy {
+                    out.push_str(&branch);
+                }
+                // render_block stopped on EndIf; consume it.
+                if *i < tokens.len() {
+                    *i += 1;
+                }
+            }
+            Token::EndIf => return,
+            Token::For(item, list) => {
+                *i += 1;
+                let body_start = *i;
+                // First pass: find the body span by rendering with an empty
+                // scope throwaway is wasteful, so instead scan for matching
+                // ENDFOR, then render the body once per row.
+                let body_end = find_matching_endfor(tokens, body_start);
+                let rows = match resolve(scope, list) {
+                    Some(Value::List(rows)) => rows.clone(),
+                    _ => Vec::new(),
+                };
+                for row in &rows {
+                    let mut row_scope = scope.clone();
+                    for (k, v) in row {
+                        row_scope.insert(format!("{}.{}", item, k), v.clone());
+                    }
+                    let mut body_i = body_start;
+                    render_block(&tokens[..body_end], &mut body_i, &row_scope, out);
+                }
+                *i = body_end + 1; // skip past ENDFOR
+            }
+            Token::EndFor => return,
+        }
+    }
+}
+
+fn find_matching_endfor(tokens: &[Token], start: usize) -> usize {
+    let mut depth = 0;
+    let mut j = start;
+    while j < tokens.len() {
+        match &tokens[j] {
+            Token::For(_, _) => depth += 1,
+            Token::EndFor => {
+                if depth == 0 {
+                    return j;
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+        j += 1;
+    }
+    tokens.len()
+}
+
+fn resolve<'a>(scope: &'a HashMap<String, Value>, name: &str) -> Option<&'a Value> {
+    scope.get(name)
+}
@@ -0,0 +1,127 @@
+// Runs every generated NPB kernel binary across its supported problem
+// classes, checking that each run reports a successful verification and
+// recording how long it took. Intended as a quick "did the last change
+// break anything" smoke test, not a replacement for the kernels' own
+// built-in verification against the NPB reference values.
+
+use std::env;
+use std::process::Command;
+use std::time::Instant;
+
+struct KernelRun {
+    /// Binary name as built by cargo, e.g. "ep-s" or "cg-pp-b".
+    binary: String,
+    /// Extra args after the binary name (class/thread count), if any.
+    args: Vec<String>,
+}
+
+struct RunOutcome {
+    run: String,
+    verified: bool,
+    elapsed_secs: f64,
+}
+
+fn targets() -> Vec<KernelRun> {
+    let classes = ["s", "w", "a", "b"];
+    let mut runs = Vec::new();
+
+    for class in classes {
+        runs.push(KernelRun { binary: format!("ep-{}", class), args: vec![] });
+        runs.push(KernelRun { binary: format!("cg-{}", class), args: vec![] });
+    }
+
+    // The "-pp" binaries are generic: class and thread count are runtime args.
+    for class in classes {
+        runs.push(KernelRun {
+            binary: "ep-pp".to_string(),
+            args: vec![class.to_uppercase(), "4".to_string()],
+        });
+        runs.push(KernelRun {
+            binary: "is".to_string(),
+            args: vec![class.to_uppercase(), "4".to_string()],
+        });
+    }
+
+    // cg-pp-b hardcodes CLASS = "b" internally and only takes a thread count,
+    // so it runs once rather than once per class.
+    runs.push(KernelRun {
+        binary: "cg-pp-b".to_string(),
+        args: vec!["4".to_string()],
+    });
+
+    runs
+}
+
+fn run_one(kernel: &KernelRun) -> Option<RunOutcome> {
+    let run_label = if kernel.args.is_empty() {
+        kernel.binary.clone()
+    } else {
+        format!("{} {}", kernel.binary, kernel.args.join(" "))
+    };
+
+    println!("Running {} ...", run_label);
+
+    let start = Instant::now();
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--bin", &kernel.binary, "--"])
+        .args(&kernel.args)
+        .output();
+    let elapsed_secs = start.elapsed().as_secs_f64();
+
+    let output = match output {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("  failed to launch {}: {}", run_label, e);
+            return None;
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let verified = stdout.contains("SUCCESSFUL") || stdout.contains("verified = true");
+
+    if !output.status.success() {
+        eprintln!("  {} exited with {}", run_label, output.status);
+    }
+
+    Some(RunOutcome { run: run_label, verified, elapsed_secs })
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let only_kernel = args.get(1).cloned();
+
+    let mut outcomes = Vec::new();
+    for target in targets() {
+        if let Some(filter) = &only_kernel {
+            if !target.binary.starts_with(filter.as_str()) {
+                continue;
+            }
+        }
+        if let Some(outcome) = run_one(&target) {
+            outcomes.push(outcome);
+        }
+    }
+
+    println!("\n{:-<60}", "");
+    println!("VERIFICATION SUMMARY");
+    println!("{:-<60}", "");
+
+    let mut failures = 0;
+    for outcome in &outcomes {
+        println!(
+            "  {:<20} {:>10.3}s  {}",
+            outcome.run,
+            outcome.elapsed_secs,
+            if outcome.verified { "PASS" } else { "FAIL" }
+        );
+        if !outcome.verified {
+            failures += 1;
+        }
+    }
+
+    println!("\n{} run(s), {} failure(s)", outcomes.len(), failures);
+
+    if failures > 0 {
+        std::process::exit(1);
+    }
+}
@@ -1,3 +1,20 @@
+// The `ISBenchmark` core (struct, `create_seq`, `rank`, `partial_verify`,
+// `full_verify`) is written against `alloc` only, so it builds with
+// `--no-default-features` on targets without `std` -- a bare-metal or WASM
+// harness supplies its own `#[global_allocator]` (a bump allocator, an
+// arena, whatever fits the target) and drives the core directly instead of
+// through `main`. `std` is a default feature; disabling it also drops the
+// Rayon parallelism, the Arrow export, and `main` itself, none of which are
+// `alloc`-only, leaving just the serial sort kernel.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 const COMPILETIME: &str = %% COMPILE_TIME %%;
 const NPBVERSION: &str = "4.1";
 const COMPILERVERSION: &str = "rustc 1.70.0-nightly";
@@ -6,20 +23,208 @@ const LIBVERSION: &str = "1.0";
 const MAX_ITERATIONS: i32 = 10;
 const TEST_ARRAY_SIZE: usize = 5;
 
-use common::print_results;
 use common::randdp;
+#[cfg(feature = "std")]
+use common::print_results;
+#[cfg(feature = "std")]
 use std::time::Instant;
+#[cfg(feature = "std")]
 use std::env;
+#[cfg(feature = "std")]
 use rayon::prelude::*;
-use std::sync::atomic::{AtomicI64, Ordering};
 
 type KeyType = i64;
 
+/// Arrow IPC export of the IS benchmark's numeric results, enabled via
+/// `--features arrow` with `--output arrow <path>`. Writes the per-iteration
+/// telemetry (`iteration`, `time_seconds`, `mops`, `partial_verify_passed`)
+/// that `main`'s iteration loop used to just print as a bare number, as a
+/// `RecordBatch` via the Arrow file writer -- so a run can be loaded
+/// straight into pandas/Polars/DuckDB for regression tracking instead of
+/// grep-parsed stdout. `--dump-ranks` additionally writes the final
+/// `key_buff_ptr_global` rank array and the bucket occupancy derived from
+/// `bucket_ptrs` to sibling files (an IPC file carries one schema, so the
+/// differently-shaped column sets can't share a single file).
+#[cfg(all(feature = "arrow", feature = "std"))]
+mod results {
+    use arrow::array::{Float64Array, Int32Array, Int64Array};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::error::ArrowError;
+    use arrow::ipc::writer::FileWriter;
+    use arrow::record_batch::RecordBatch;
+    use std::fs::File;
+    use std::sync::Arc;
+
+    pub struct IterationRecord {
+        pub iteration: i32,
+        pub time_seconds: f64,
+        pub mops: f64,
+        pub partial_verify_passed: i32,
+    }
+
+    pub fn write_iterations(path: &str, records: &[IterationRecord]) -> Result<(), ArrowError> {
+        let schema = Schema::new(vec![
+            Field::new("iteration", DataType::Int32, false),
+            Field::new("time_seconds", DataType::Float64, false),
+            Field::new("mops", DataType::Float64, false),
+            Field::new("partial_verify_passed", DataType::Int32, false),
+        ]);
+
+        let iteration: Int32Array = records.iter().map(|r| r.iteration).collect();
+        let time_seconds: Float64Array = records.iter().map(|r| r.time_seconds).collect();
+        let mops: Float64Array = records.iter().map(|r| r.mops).collect();
+        let partial_verify_passed: Int32Array =
+            records.iter().map(|r| r.partial_verify_passed).collect();
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(iteration),
+                Arc::new(time_seconds),
+                Arc::new(mops),
+                Arc::new(partial_verify_passed),
+            ],
+        )?;
+
+        let file = File::create(path)?;
+        let mut writer = FileWriter::try_new(file, &schema)?;
+        writer.write(&batch)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Writes the final rank array to `path` and, as a separate IPC file
+    /// named `<path>.buckets`, each bucket's key count derived from the
+    /// cumulative `bucket_ptrs` array.
+    pub fn write_rank_dump(
+        path: &str,
+        key_buff_ptr_global: &[i64],
+        bucket_ptrs: &[i64],
+    ) -> Result<(), ArrowError> {
+        let rank_schema = Schema::new(vec![
+            Field::new("key", DataType::Int32, false),
+            Field::new("rank", DataType::Int64, false),
+        ]);
+        let key: Int32Array = (0..key_buff_ptr_global.len() as i32).collect();
+        let rank: Int64Array = key_buff_ptr_global.iter().copied().collect();
+        let rank_batch = RecordBatch::try_new(
+            Arc::new(rank_schema.clone()),
+            vec![Arc::new(key), Arc::new(rank)],
+        )?;
+
+        let file = File::create(path)?;
+        let mut writer = FileWriter::try_new(file, &rank_schema)?;
+        writer.write(&rank_batch)?;
+        writer.finish()?;
+
+        let occupancy: Vec<i64> = (1..bucket_ptrs.len())
+            .map(|i| bucket_ptrs[i] - bucket_ptrs[i - 1])
+            .collect();
+        let bucket_schema = Schema::new(vec![
+            Field::new("bucket", DataType::Int32, false),
+            Field::new("count", DataType::Int64, false),
+        ]);
+        let bucket: Int32Array = (0..occupancy.len() as i32).collect();
+        let count: Int64Array = occupancy.into_iter().collect();
+        let bucket_batch = RecordBatch::try_new(
+            Arc::new(bucket_schema.clone()),
+            vec![Arc::new(bucket), Arc::new(count)],
+        )?;
+
+        let buckets_path = format!("{}.buckets", path);
+        let buckets_file = File::create(&buckets_path)?;
+        let mut buckets_writer = FileWriter::try_new(buckets_file, &bucket_schema)?;
+        buckets_writer.write(&bucket_batch)?;
+        buckets_writer.finish()?;
+
+        Ok(())
+    }
+}
+
 #[repr(align(64))]
 struct CacheAligned<T> {
     data: T,
 }
 
+/// Classic Blelloch work-efficient parallel exclusive prefix sum over
+/// `values`: padded to a power of two, reduced bottom-up (up-sweep), then
+/// swept back down turning each slot into the sum of everything before it.
+/// Returns the per-bucket exclusive offsets (same length as `values`) and
+/// the grand total, the two numbers `rank()`'s bucket-pointer pass needs.
+#[cfg(feature = "std")]
+fn parallel_exclusive_scan(values: &[KeyType]) -> (Vec<KeyType>, KeyType) {
+    let n = values.len();
+    if n == 0 {
+        return (Vec::new(), 0);
+    }
+
+    let size = n.next_power_of_two();
+    let mut tree: Vec<KeyType> = vec![0; size];
+    tree[..n].copy_from_slice(values);
+
+    let mut d = 1;
+    while d < size {
+        let step = d * 2;
+        tree.par_chunks_mut(step).for_each(|chunk| {
+            if chunk.len() == step {
+                chunk[step - 1] += chunk[d - 1];
+            }
+        });
+        d = step;
+    }
+
+    let total = tree[size - 1];
+    tree[size - 1] = 0;
+
+    let mut d = size / 2;
+    while d >= 1 {
+        let step = d * 2;
+        tree.par_chunks_mut(step).for_each(|chunk| {
+            if chunk.len() == step {
+                let left = chunk[d - 1];
+                chunk[d - 1] = chunk[step - 1];
+                chunk[step - 1] += left;
+            }
+        });
+        d /= 2;
+    }
+
+    tree.truncate(n);
+    (tree, total)
+}
+
+/// Parallel-safe view over `key_buff2` for `parallel_scatter`: every task's
+/// `(thread, bucket)` write range was computed disjointly by
+/// `thread_bucket_offsets`, so concurrent writers never alias -- the same
+/// "we proved disjointness, trust it" tradeoff the CG SpMV kernel's
+/// `get_unchecked` usage makes.
+#[cfg(feature = "std")]
+struct DisjointKeySlots {
+    ptr: *mut KeyType,
+    len: usize,
+}
+
+#[cfg(feature = "std")]
+unsafe impl Sync for DisjointKeySlots {}
+
+#[cfg(feature = "std")]
+impl DisjointKeySlots {
+    fn new(slice: &mut [KeyType]) -> Self {
+        Self {
+            ptr: slice.as_mut_ptr(),
+            len: slice.len(),
+        }
+    }
+
+    /// # Safety
+    /// `pos` must be `< len`, and no two concurrent calls may target the
+    /// same `pos`.
+    unsafe fn write(&self, pos: usize, value: KeyType) {
+        debug_assert!(pos < self.len);
+        *self.ptr.add(pos) = value;
+    }
+}
+
 struct ISBenchmark {
     class_char: String,
     total_keys: usize,
@@ -109,14 +314,16 @@ impl ISBenchmark {
                 self.test_rank_array = [1, 36538729, 1978098519, 2145192618, 2147425337];
             }
             _ => {
+                #[cfg(feature = "std")]
                 eprintln!("Warning: Unknown class '{}' for verification arrays.", self.class_char);
             }
         }
     }
     
+    #[cfg(feature = "std")]
     fn create_seq(&mut self) {
         let chunk_size = (self.total_keys + self.num_threads - 1) / self.num_threads;
-        
+
         let key_chunks: Vec<(usize, Vec<KeyType>)> = (0..self.num_threads)
             .into_par_iter()
             .filter_map(|thread_id| {
@@ -160,7 +367,43 @@ impl ISBenchmark {
             }
         }
     }
-    
+
+    /// Serial fallback used when the `std`/Rayon thread pool isn't
+    /// available (see the crate-level `no_std` doc comment): walks the same
+    /// per-thread seed chunks as the Rayon version above, one at a time.
+    #[cfg(not(feature = "std"))]
+    fn create_seq(&mut self) {
+        let chunk_size = (self.total_keys + self.num_threads - 1) / self.num_threads;
+
+        for thread_id in 0..self.num_threads {
+            let start_idx = thread_id * chunk_size;
+            let end_idx = (start_idx + chunk_size).min(self.total_keys);
+
+            if start_idx >= self.total_keys {
+                continue;
+            }
+
+            let mut seed = Self::find_my_seed(
+                thread_id as i32,
+                self.num_threads as i32,
+                (4 * self.total_keys) as i64,
+                314159265.0,
+                1220703125.0,
+            );
+
+            let k = (self.max_key / 4) as f64;
+
+            for idx in start_idx..end_idx {
+                let mut x = randdp::randlc(&mut seed, 1220703125.0);
+                x += randdp::randlc(&mut seed, 1220703125.0);
+                x += randdp::randlc(&mut seed, 1220703125.0);
+                x += randdp::randlc(&mut seed, 1220703125.0);
+
+                self.key_array[idx] = (k * x) as KeyType;
+            }
+        }
+    }
+
     fn find_my_seed(kn: i32, np: i32, nn: i64, s: f64, a: f64) -> f64 {
         if kn == 0 {
             return s;
@@ -217,85 +460,94 @@ impl ISBenchmark {
             }
         }
         
-        self.bucket_ptrs[0] = 0;
-        for i in 1..self.num_buckets {
-            self.bucket_ptrs[i] = self.bucket_ptrs[i-1];
-            for k in 0..self.num_threads {
-                self.bucket_ptrs[i] += self.bucket_size[k].data[i-1];
+        // Replaces a serial O(num_buckets*num_threads) offset pass and a
+        // serial scatter with a parallel exclusive scan over bucket totals
+        // plus a scatter where every (thread, bucket) write target was
+        // precomputed to be disjoint. `bucket_ptrs[num_buckets]` still ends
+        // up equal to `total_keys`, same as the old duplicated-last-slot
+        // bookkeeping below. No Rayon on the `no_std`/alloc-only build, so
+        // it keeps the original nested-loop version.
+        #[cfg(feature = "std")]
+        {
+            let bucket_totals: Vec<KeyType> = (0..self.num_buckets)
+                .into_par_iter()
+                .map(|b| (0..self.num_threads).map(|t| self.bucket_size[t].data[b]).sum())
+                .collect();
+
+            let (exclusive, total) = parallel_exclusive_scan(&bucket_totals);
+
+            let thread_offsets = self.thread_bucket_offsets(&exclusive);
+            self.parallel_scatter(chunk_size, shift, thread_offsets);
+
+            for i in 0..self.num_buckets {
+                self.bucket_ptrs[i] = exclusive[i] + bucket_totals[i];
             }
+            self.bucket_ptrs[self.num_buckets] = total;
         }
-        
-        let mut bucket_offsets = vec![vec![0; self.num_buckets]; self.num_threads];
-        for thread_id in 0..self.num_threads {
-            for i in 0..self.num_buckets {
-                bucket_offsets[thread_id][i] = self.bucket_ptrs[i];
-                for prev_thread in 0..thread_id {
-                    bucket_offsets[thread_id][i] += self.bucket_size[prev_thread].data[i];
+        #[cfg(not(feature = "std"))]
+        {
+            self.bucket_ptrs[0] = 0;
+            for i in 1..self.num_buckets {
+                self.bucket_ptrs[i] = self.bucket_ptrs[i-1];
+                for k in 0..self.num_threads {
+                    self.bucket_ptrs[i] += self.bucket_size[k].data[i-1];
                 }
             }
-        }
-        
-        for (thread_id, chunk) in self.key_array.chunks(chunk_size).enumerate() {
-            if thread_id < self.num_threads {
-                for &key in chunk {
-                    let bucket_idx = (key >> shift) as usize;
-                    if bucket_idx < self.num_buckets {
-                        let pos = bucket_offsets[thread_id][bucket_idx] as usize;
-                        if pos < self.total_keys {
-                            self.key_buff2[pos] = key;
-                            bucket_offsets[thread_id][bucket_idx] += 1;
-                        }
+
+            let mut bucket_offsets = vec![vec![0; self.num_buckets]; self.num_threads];
+            for thread_id in 0..self.num_threads {
+                for i in 0..self.num_buckets {
+                    bucket_offsets[thread_id][i] = self.bucket_ptrs[i];
+                    for prev_thread in 0..thread_id {
+                        bucket_offsets[thread_id][i] += self.bucket_size[prev_thread].data[i];
                     }
                 }
             }
-        }
-        
-        for i in 0..self.num_buckets {
-            self.bucket_ptrs[i] = 0;
-            for k in 0..self.num_threads {
-                self.bucket_ptrs[i] += self.bucket_size[k].data[i];
+
+            for (thread_id, chunk) in self.key_array.chunks(chunk_size).enumerate() {
+                if thread_id < self.num_threads {
+                    for &key in chunk {
+                        let bucket_idx = (key >> shift) as usize;
+                        if bucket_idx < self.num_buckets {
+                            let pos = bucket_offsets[thread_id][bucket_idx] as usize;
+                            if pos < self.total_keys {
+                                self.key_buff2[pos] = key;
+                                bucket_offsets[thread_id][bucket_idx] += 1;
+                            }
+                        }
+                    }
+                }
             }
-            if i > 0 {
-                self.bucket_ptrs[i] += self.bucket_ptrs[i-1];
+
+            for i in 0..self.num_buckets {
+                self.bucket_ptrs[i] = 0;
+                for k in 0..self.num_threads {
+                    self.bucket_ptrs[i] += self.bucket_size[k].data[i];
+                }
+                if i > 0 {
+                    self.bucket_ptrs[i] += self.bucket_ptrs[i-1];
+                }
             }
+            self.bucket_ptrs[self.num_buckets] = self.bucket_ptrs[self.num_buckets - 1];
         }
-        self.bucket_ptrs[self.num_buckets] = self.bucket_ptrs[self.num_buckets - 1];
-        
+
         let start_indices: Vec<KeyType> = (0..self.num_buckets)
             .map(|i| if i == 0 { 0 } else { self.bucket_ptrs[i - 1] })
             .collect();
         
+        // Rayon's per-bucket fan-out needs `std`; the serial fallback below
+        // calls the same `bucket_segment` helper one bucket at a time so
+        // the no_std/alloc-only build still produces identical buffers.
+        #[cfg(feature = "std")]
         let bucket_results: Vec<Vec<KeyType>> = (0..self.num_buckets)
             .into_par_iter()
-            .map(|i| {
-                let k1 = i * num_bucket_keys;
-                let k2 = (k1 + num_bucket_keys).min(self.max_key);
-                let segment_len = k2 - k1;
-                
-                let mut segment = vec![0; segment_len];
-                
-                let start_ptr = if i > 0 { self.bucket_ptrs[i - 1] } else { 0 };
-                let end_ptr = self.bucket_ptrs[i];
-                
-                for j in start_ptr..end_ptr {
-                    let key = self.key_buff2[j as usize] as usize;
-                    if key >= k1 && key < k2 {
-                        segment[key - k1] += 1;
-                    }
-                }
-                
-                if !segment.is_empty() {
-                    segment[0] += start_indices[i];
-                }
-                
-                for idx in 1..segment_len {
-                    segment[idx] += segment[idx - 1];
-                }
-                
-                segment
-            })
+            .map(|i| self.bucket_segment(i, num_bucket_keys, &start_indices))
             .collect();
-    
+        #[cfg(not(feature = "std"))]
+        let bucket_results: Vec<Vec<KeyType>> = (0..self.num_buckets)
+            .map(|i| self.bucket_segment(i, num_bucket_keys, &start_indices))
+            .collect();
+
         for (i, segment) in bucket_results.into_iter().enumerate() {
             let k1 = i * num_bucket_keys;
             let k2 = (k1 + num_bucket_keys).min(self.max_key);
@@ -308,12 +560,119 @@ impl ISBenchmark {
         }
         
         self.partial_verify(iteration);
-        
+
         if iteration == MAX_ITERATIONS {
             self.key_buff_ptr_global = self.key_buff1.clone();
         }
     }
-    
+
+    /// Builds one bucket's slice of `key_buff1`: a count of `key_buff2`'s
+    /// keys falling in `[k1, k2)`, turned into an inclusive prefix sum
+    /// seeded with `start_indices[i]`. Pulled out of `rank` so the Rayon
+    /// and serial (no `std`) variants of the per-bucket fan-out can share
+    /// it instead of duplicating the body.
+    fn bucket_segment(
+        &self,
+        i: usize,
+        num_bucket_keys: usize,
+        start_indices: &[KeyType],
+    ) -> Vec<KeyType> {
+        let k1 = i * num_bucket_keys;
+        let k2 = (k1 + num_bucket_keys).min(self.max_key);
+        let segment_len = k2 - k1;
+
+        let mut segment = vec![0; segment_len];
+
+        let start_ptr = if i > 0 { self.bucket_ptrs[i - 1] } else { 0 };
+        let end_ptr = self.bucket_ptrs[i];
+
+        for j in start_ptr..end_ptr {
+            let key = self.key_buff2[j as usize] as usize;
+            if key >= k1 && key < k2 {
+                segment[key - k1] += 1;
+            }
+        }
+
+        if !segment.is_empty() {
+            segment[0] += start_indices[i];
+        }
+
+        for idx in 1..segment_len {
+            segment[idx] += segment[idx - 1];
+        }
+
+        segment
+    }
+
+    /// Each `(thread, bucket)` pair's private write cursor into
+    /// `key_buff2`: bucket `b`'s region starts at `exclusive[b]`, and
+    /// thread `t`'s slice of it starts after however many keys threads
+    /// `0..t` already contributed to that bucket. Computed with the bucket
+    /// dimension as the parallel axis (`num_buckets` is usually far bigger
+    /// than `num_threads`) instead of nesting a `num_threads` loop inside a
+    /// serial walk over buckets, then transposed into thread-major order so
+    /// `parallel_scatter` can hand each task a flat cursor vector.
+    #[cfg(feature = "std")]
+    fn thread_bucket_offsets(&self, exclusive: &[KeyType]) -> Vec<Vec<KeyType>> {
+        let by_bucket: Vec<Vec<KeyType>> = (0..self.num_buckets)
+            .into_par_iter()
+            .map(|b| {
+                let mut running = exclusive[b];
+                (0..self.num_threads)
+                    .map(|t| {
+                        let offset = running;
+                        running += self.bucket_size[t].data[b];
+                        offset
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut by_thread = vec![vec![0 as KeyType; self.num_buckets]; self.num_threads];
+        for (b, column) in by_bucket.into_iter().enumerate() {
+            for (t, offset) in column.into_iter().enumerate() {
+                by_thread[t][b] = offset;
+            }
+        }
+        by_thread
+    }
+
+    /// Scatters `key_array` into `key_buff2` with each thread's chunk run
+    /// as an independent Rayon task: `thread_offsets[t]` gives thread `t`'s
+    /// starting cursor for every bucket, computed by `thread_bucket_offsets`
+    /// so no two tasks ever target the same slot.
+    #[cfg(feature = "std")]
+    fn parallel_scatter(&mut self, chunk_size: usize, shift: u32, thread_offsets: Vec<Vec<KeyType>>) {
+        let total_keys = self.total_keys;
+        let num_buckets = self.num_buckets;
+        let num_threads = self.num_threads;
+        let key_array = &self.key_array;
+        let slots = DisjointKeySlots::new(&mut self.key_buff2);
+
+        key_array
+            .par_chunks(chunk_size)
+            .zip(thread_offsets.into_par_iter())
+            .take(num_threads)
+            .for_each(|(chunk, mut offsets)| {
+                for &key in chunk {
+                    let bucket_idx = (key >> shift) as usize;
+                    if bucket_idx < num_buckets {
+                        let pos = offsets[bucket_idx] as usize;
+                        if pos < total_keys {
+                            // SAFETY: `offsets` was built by
+                            // `thread_bucket_offsets` so this task's
+                            // per-bucket cursor starts at a base disjoint
+                            // from every other task's, and only increases
+                            // within this closure -- no two `(thread,
+                            // bucket)` pairs ever write the same `pos`.
+                            unsafe { slots.write(pos, key) };
+                            offsets[bucket_idx] += 1;
+                        }
+                    }
+                }
+            });
+    }
+
     fn partial_verify(&mut self, iteration: i32) {
         for i in 0..TEST_ARRAY_SIZE {
             let k = self.partial_verify_vals[i];
@@ -421,6 +780,7 @@ impl ISBenchmark {
                 }
                 
                 if failed {
+                    #[cfg(feature = "std")]
                     println!("Failed partial verification: iteration {}, test key {}", iteration, i);
                 }
             }
@@ -453,6 +813,7 @@ impl ISBenchmark {
         }
         
         if error_count != 0 {
+            #[cfg(feature = "std")]
             println!("Full_verify: number of keys out of sort: {}", error_count);
         } else {
             self.passed_verification += 1;
@@ -460,16 +821,109 @@ impl ISBenchmark {
     }
 }
 
+/// A single iteration's outcome, yielded by `run_streaming` the moment that
+/// iteration's `rank()` call returns rather than after the whole
+/// `MAX_ITERATIONS` loop -- so a long class-D run can report progress (or a
+/// consumer can abort) incrementally instead of the current bare iteration
+/// number. Timed with `Instant`, so this -- like the rest of the driver
+/// below -- only exists when `std` is available.
+#[cfg(feature = "std")]
+struct IterationOutcome {
+    iteration: i32,
+    time_seconds: f64,
+    partial_verify_passed: i32,
+}
+
+/// Iterator returned by `run_streaming`: each `.next()` drives exactly one
+/// more `rank()` call and yields its [`IterationOutcome`] immediately.
+#[cfg(feature = "std")]
+struct StreamingRun<'a> {
+    benchmark: &'a mut ISBenchmark,
+    next_iteration: i32,
+}
+
+#[cfg(feature = "std")]
+impl<'a> Iterator for StreamingRun<'a> {
+    type Item = IterationOutcome;
+
+    fn next(&mut self) -> Option<IterationOutcome> {
+        if self.next_iteration > MAX_ITERATIONS {
+            return None;
+        }
+        let iteration = self.next_iteration;
+        self.next_iteration += 1;
+
+        let start = Instant::now();
+        let passed_before = self.benchmark.passed_verification;
+        self.benchmark.rank(iteration);
+        Some(IterationOutcome {
+            iteration,
+            time_seconds: start.elapsed().as_secs_f64(),
+            partial_verify_passed: self.benchmark.passed_verification - passed_before,
+        })
+    }
+}
+
+/// Two ways to drive the fixed `1..=MAX_ITERATIONS` `rank()` loop, modeled
+/// on a SyncClient/AsyncClient split: `run_blocking` runs the whole thing
+/// and returns the total elapsed time exactly as `main` always has, while
+/// `run_streaming` hands back an iterator that reports each iteration's
+/// timing and partial-verify outcome as soon as it lands. A reusable driver
+/// other NPB kernels in this crate could implement too, not just IS.
+#[cfg(feature = "std")]
+trait Benchmark {
+    fn run_blocking(&mut self) -> f64;
+    fn run_streaming(&mut self) -> StreamingRun<'_>;
+}
+
+#[cfg(feature = "std")]
+impl Benchmark for ISBenchmark {
+    fn run_blocking(&mut self) -> f64 {
+        let timer = Instant::now();
+        for iteration in 1..=MAX_ITERATIONS {
+            self.rank(iteration);
+        }
+        timer.elapsed().as_secs_f64()
+    }
+
+    fn run_streaming(&mut self) -> StreamingRun<'_> {
+        StreamingRun {
+            benchmark: self,
+            next_iteration: 1,
+        }
+    }
+}
+
+/// Thin `std`-only shim: owns everything the `alloc`-only core above can't
+/// (`env::args`, `Instant`, stdout/stderr, the Rayon pool, the Arrow
+/// writers) and drives `ISBenchmark` through it. A `no_std` embedder
+/// supplies its own entry point and `#[global_allocator]` and constructs
+/// `ISBenchmark` directly instead of calling this.
+#[cfg(feature = "std")]
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     if args.len() < 3 {
-        eprintln!("Usage: {} <CLASS> <NUM_THREADS>", args.get(0).map_or("is", |s| s.as_str()));
+        eprintln!("Usage: {} <CLASS> <NUM_THREADS> [--output arrow <path>] [--dump-ranks] [--stream]", args.get(0).map_or("is", |s| s.as_str()));
         eprintln!("Example: {} S 4", args.get(0).map_or("is", |s| s.as_str()));
         eprintln!("Available classes: S, W, A, B, C, D");
         std::process::exit(1);
     }
 
+    // Arrow IPC export of the per-iteration telemetry (and, with
+    // `--dump-ranks`, the final rank/bucket arrays); requires building with
+    // `--features arrow`, see the `results` module above.
+    let output_arrow_path: Option<String> = args
+        .iter()
+        .position(|a| a == "--output")
+        .filter(|&i| args.get(i + 1).map(String::as_str) == Some("arrow"))
+        .and_then(|i| args.get(i + 2))
+        .cloned();
+    let dump_ranks = args.iter().any(|a| a == "--dump-ranks");
+    // Drives the iteration loop via `Benchmark::run_streaming` instead of a
+    // bare `for` loop, so progress is reported as each iteration lands.
+    let stream_mode = args.iter().any(|a| a == "--stream");
+
     let class_arg = &args[1];
     let class_npb_val: String = class_arg.to_uppercase();
 
@@ -533,14 +987,63 @@ fn main() {
     }
     
     let bench_timer = Instant::now();
-    
-    for iteration in 1..=MAX_ITERATIONS {
-        if class_npb_val != "S" {
-            println!("        {}", iteration);
+
+    #[cfg(feature = "arrow")]
+    let mut iter_records: Vec<results::IterationRecord> = Vec::with_capacity(MAX_ITERATIONS as usize);
+
+    if stream_mode {
+        for outcome in benchmark.run_streaming() {
+            if class_npb_val != "S" {
+                println!(
+                    "        {}  ({:.6}s, {} verified)",
+                    outcome.iteration, outcome.time_seconds, outcome.partial_verify_passed
+                );
+            }
+            #[cfg(feature = "arrow")]
+            {
+                let iter_mops = if outcome.time_seconds > 0.0 {
+                    total_keys_val as f64 / outcome.time_seconds / 1_000_000.0
+                } else {
+                    0.0
+                };
+                iter_records.push(results::IterationRecord {
+                    iteration: outcome.iteration,
+                    time_seconds: outcome.time_seconds,
+                    mops: iter_mops,
+                    partial_verify_passed: outcome.partial_verify_passed,
+                });
+            }
+        }
+    } else {
+        for iteration in 1..=MAX_ITERATIONS {
+            if class_npb_val != "S" {
+                println!("        {}", iteration);
+            }
+            #[cfg(feature = "arrow")]
+            let iter_timer = Instant::now();
+            #[cfg(feature = "arrow")]
+            let passed_before = benchmark.passed_verification;
+
+            benchmark.rank(iteration);
+
+            #[cfg(feature = "arrow")]
+            {
+                let iter_time = iter_timer.elapsed().as_secs_f64();
+                let iter_mops = if iter_time > 0.0 {
+                    total_keys_val as f64 / iter_time / 1_000_000.0
+                } else {
+                    0.0
+                };
+                iter_records.push(results::IterationRecord {
+                    iteration,
+                    time_seconds: iter_time,
+                    mops: iter_mops,
+                    partial_verify_passed: benchmark.passed_verification - passed_before,
+                });
+            }
         }
-        benchmark.rank(iteration);
     }
-    
+
     let timecounter = bench_timer.elapsed().as_secs_f64();
     
     benchmark.full_verify();
@@ -551,7 +1054,34 @@ fn main() {
     } else {
         0.0
     };
-    
+
+    if let Some(path) = &output_arrow_path {
+        #[cfg(feature = "arrow")]
+        {
+            if let Err(e) = results::write_iterations(path, &iter_records) {
+                eprintln!("Failed to write Arrow IPC output to {}: {}", path, e);
+            } else {
+                println!(" Wrote per-iteration telemetry to {} (Arrow IPC)", path);
+            }
+            if dump_ranks {
+                if let Err(e) = results::write_rank_dump(
+                    &format!("{}.ranks", path),
+                    &benchmark.key_buff_ptr_global,
+                    &benchmark.bucket_ptrs,
+                ) {
+                    eprintln!("Failed to write Arrow IPC rank dump for {}: {}", path, e);
+                } else {
+                    println!(" Wrote rank/bucket dump to {}.ranks (+ .buckets) (Arrow IPC)", path);
+                }
+            }
+        }
+        #[cfg(not(feature = "arrow"))]
+        {
+            let _ = dump_ranks;
+            eprintln!("--output arrow requires building with --features arrow; ignoring.");
+        }
+    }
+
     print_results::rust_print_results(
         "IS",
         &class_npb_val,
@@ -0,0 +1,120 @@
+// Fixed-bucket logarithmic latency histogram.
+//
+// Storing every sample would distort the memory-usage numbers this same
+// benchmark reports, so instead we keep a small array of `AtomicU64`
+// counters: one power-of-two bucket per order of magnitude, refined with a
+// few linear sub-buckets for resolution. Recording is O(1) and lock-free;
+// reading out percentiles walks the (small, fixed-size) bucket array.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Sub-buckets per power-of-two range (e.g. [2^k, 2^(k+1))).
+const SUBBUCKETS: u32 = 4;
+const NUM_BUCKETS: usize = 64 * SUBBUCKETS as usize;
+
+#[derive(Debug)]
+pub struct LatencyHistogram {
+    buckets: Vec<AtomicU64>,
+    max_ns: AtomicU64,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self {
+            buckets: (0..NUM_BUCKETS).map(|_| AtomicU64::new(0)).collect(),
+            max_ns: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(&self, duration_ns: u64) {
+        let idx = bucket_index(duration_ns);
+        self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+        self.max_ns.fetch_max(duration_ns, Ordering::Relaxed);
+    }
+
+    pub fn max_ns(&self) -> u64 {
+        self.max_ns.load(Ordering::Relaxed)
+    }
+
+    pub fn total_count(&self) -> u64 {
+        self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).sum()
+    }
+
+    /// Representative value (geometric midpoint of its bucket's range) for
+    /// percentile `p` in `[0.0, 1.0]`.
+    pub fn percentile(&self, p: f64) -> u64 {
+        let total = self.total_count();
+        if total == 0 {
+            return 0;
+        }
+        let target_rank = ((p * total as f64).ceil() as u64).max(1);
+
+        let mut cumulative = 0u64;
+        for (idx, bucket) in self.buckets.iter().enumerate() {
+            cumulative += bucket.load(Ordering::Relaxed);
+            if cumulative >= target_rank {
+                return bucket_midpoint(idx);
+            }
+        }
+        self.max_ns()
+    }
+
+    /// Convenience for printing p50/p90/p95/p99 + max in one call.
+    pub fn summary(&self) -> LatencySummary {
+        LatencySummary {
+            p50_ns: self.percentile(0.50),
+            p90_ns: self.percentile(0.90),
+            p95_ns: self.percentile(0.95),
+            p99_ns: self.percentile(0.99),
+            max_ns: self.max_ns(),
+            count: self.total_count(),
+        }
+    }
+}
+
+pub struct LatencySummary {
+    pub p50_ns: u64,
+    pub p90_ns: u64,
+    pub p95_ns: u64,
+    pub p99_ns: u64,
+    pub max_ns: u64,
+    pub count: u64,
+}
+
+impl LatencySummary {
+    pub fn print(&self, label: &str) {
+        if self.count == 0 {
+            return;
+        }
+        println!("\n{} LATENCY PERCENTILES:", label);
+        println!("  p50: {:.2} us", self.p50_ns as f64 / 1000.0);
+        println!("  p90: {:.2} us", self.p90_ns as f64 / 1000.0);
+        println!("  p95: {:.2} us", self.p95_ns as f64 / 1000.0);
+        println!("  p99: {:.2} us", self.p99_ns as f64 / 1000.0);
+        println!("  max: {:.2} us", self.max_ns as f64 / 1000.0);
+    }
+}
+
+/// Bucket layout: within power-of-two range `[2^k, 2^(k+1))`, split into
+/// `SUBBUCKETS` equal-width linear sub-buckets.
+fn bucket_index(duration_ns: u64) -> usize {
+    let d = duration_ns.max(1);
+    let k = 63 - d.leading_zeros();
+    let range_start = 1u64 << k;
+    let range_size = range_start; // 2^(k+1) - 2^k == 2^k
+    let offset_in_range = d - range_start;
+    let sub = (offset_in_range * SUBBUCKETS as u64 / range_size.max(1)) as u32;
+    let idx = k * SUBBUCKETS + sub.min(SUBBUCKETS - 1);
+    (idx as usize).min(NUM_BUCKETS - 1)
+}
+
+fn bucket_midpoint(idx: usize) -> u64 {
+    let k = idx as u32 / SUBBUCKETS;
+    let sub = idx as u32 % SUBBUCKETS;
+    let range_start = 1u64 << k;
+    let range_size = range_start;
+    let sub_start = range_start + sub as u64 * range_size / SUBBUCKETS as u64;
+    let sub_end = range_start + (sub as u64 + 1) * range_size / SUBBUCKETS as u64;
+    // Geometric midpoint of [sub_start, sub_end).
+    ((sub_start as f64 * sub_end.max(sub_start + 1) as f64).sqrt()) as u64
+}
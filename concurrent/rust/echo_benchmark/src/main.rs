@@ -1,4 +1,7 @@
-use tokio::net::{TcpListener, TcpStream};
+mod latency_histogram;
+mod report;
+
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -7,9 +10,12 @@ use std::time::{Duration, Instant};
 use tokio::sync::{Semaphore, broadcast, RwLock};
 use tokio::time::{sleep, timeout};
 use sysinfo::{System, SystemExt, ProcessExt, Pid};
-use clap::Parser;
-use rand::{thread_rng, Rng};
+use clap::{Parser, ValueEnum};
+use rand::{thread_rng, Rng, RngCore, SeedableRng};
 use rand::distributions::{Alphanumeric, DistString};
+use rand_chacha::ChaCha8Rng;
+use latency_histogram::LatencyHistogram;
+use report::{BenchmarkReport, OutputFormat};
 
 // Command line argument parsing
 #[derive(Parser, Debug)]
@@ -18,22 +24,93 @@ struct Args {
     /// Number of echo clients to spawn
     #[arg(long, default_value_t = 50)]
     num_clients: usize,
-    
+
     /// Number of messages per client
     #[arg(long, default_value_t = 100)]
     messages_per_client: usize,
-    
+
     /// Maximum number of concurrent connections allowed
     #[arg(long, default_value_t = 1000)]
     max_connections: usize,
-    
+
     /// Size of message payload in KB (0 for default small messages)
     #[arg(long, default_value_t = 0)]
     message_size_kb: usize,
-    
+
     /// Number of Tokio worker threads (0 = default based on CPU cores)
     #[arg(long, default_value_t = 0)]
     num_threads: usize,
+
+    /// Seed for deterministic payload generation (random if omitted, and
+    /// printed at startup so the run can be replayed).
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// How to render the final metrics report.
+    #[arg(long, value_enum, default_value = "text")]
+    output_format: OutputFormat,
+
+    /// Write a CPU flamegraph SVG of the benchmark run to this path.
+    /// Requires building with `--features profiling`.
+    #[arg(long, value_name = "PATH")]
+    flamegraph: Option<String>,
+
+    /// Sampling frequency (Hz) for the flamegraph profiler.
+    #[arg(long, default_value_t = 99)]
+    flamegraph_hz: i32,
+
+    /// Transport protocol to benchmark.
+    #[arg(long, value_enum, default_value = "tcp")]
+    transport: Transport,
+
+    /// Print a live throughput/connections delta line every N ms while the
+    /// benchmark runs (0 disables the sampler).
+    #[arg(long, default_value_t = 1000)]
+    stats_interval_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Transport {
+    Tcp,
+    Udp,
+}
+
+#[cfg(feature = "profiling")]
+fn start_profiler(frequency_hz: i32) -> pprof::ProfilerGuard<'static> {
+    pprof::ProfilerGuardBuilder::default()
+        .frequency(frequency_hz)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .expect("Failed to start pprof profiler")
+}
+
+#[cfg(feature = "profiling")]
+fn write_flamegraph(guard: pprof::ProfilerGuard<'static>, path: &str) {
+    match guard.report().build() {
+        Ok(report) => match std::fs::File::create(path) {
+            Ok(file) => {
+                if let Err(e) = report.flamegraph(file) {
+                    eprintln!("Failed to write flamegraph to {}: {}", path, e);
+                } else {
+                    println!("\nFlamegraph written to {}", path);
+                }
+            }
+            Err(e) => eprintln!("Failed to create {}: {}", path, e),
+        },
+        Err(e) => eprintln!("Failed to build pprof report: {}", e),
+    }
+}
+
+/// One tick of the periodic live-stats sampler: deltas against the previous
+/// tick, plus the instantaneous gauges (`active_connections`, `memory_mb`).
+#[derive(Debug, Clone, Copy)]
+struct StatsSample {
+    elapsed_secs: f64,
+    connections_per_sec: f64,
+    messages_per_sec: f64,
+    mb_per_sec: f64,
+    active_connections: usize,
+    memory_mb: f64,
 }
 
 #[derive(Debug)]
@@ -42,7 +119,11 @@ struct AsyncMetrics {
     task_spawn_times: AtomicU64, // nanoseconds
     async_operations: AtomicUsize,
     async_operation_times: AtomicU64, // nanoseconds
+    async_operation_latencies: LatencyHistogram,
     memory_snapshots: RwLock<Vec<u64>>, // KB
+    // UDP can silently drop datagrams; only populated by the UDP client path.
+    datagrams_sent: AtomicUsize,
+    datagrams_lost: AtomicUsize,
     start_time: Instant,
 }
 
@@ -53,21 +134,44 @@ impl AsyncMetrics {
             task_spawn_times: AtomicU64::new(0),
             async_operations: AtomicUsize::new(0),
             async_operation_times: AtomicU64::new(0),
+            async_operation_latencies: LatencyHistogram::new(),
             memory_snapshots: RwLock::new(Vec::new()),
+            datagrams_sent: AtomicUsize::new(0),
+            datagrams_lost: AtomicUsize::new(0),
             start_time: Instant::now(),
         }
     }
-    
+
     fn record_task_spawn(&self, duration: Duration) {
         self.task_spawns.fetch_add(1, Ordering::Relaxed);
         self.task_spawn_times.fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
     }
-    
+
     fn record_async_operation(&self, duration: Duration) {
         self.async_operations.fetch_add(1, Ordering::Relaxed);
         self.async_operation_times.fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+        self.async_operation_latencies.record(duration.as_nanos() as u64);
     }
-    
+
+    fn record_datagram_sent(&self) {
+        self.datagrams_sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_datagram_lost(&self) {
+        self.datagrams_lost.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Percentage of sent datagrams that were never echoed back within the
+    /// client's read timeout. Always 0 for TCP, since the stream path never
+    /// records sends here.
+    fn datagram_loss_pct(&self) -> f64 {
+        let sent = self.datagrams_sent.load(Ordering::Relaxed);
+        if sent == 0 {
+            return 0.0;
+        }
+        self.datagrams_lost.load(Ordering::Relaxed) as f64 / sent as f64 * 100.0
+    }
+
     async fn take_memory_snapshot(&self) {
         if let Some(memory_kb) = get_current_memory_usage() {
             let mut snapshots = self.memory_snapshots.write().await;
@@ -75,6 +179,75 @@ impl AsyncMetrics {
         }
     }
     
+    async fn build_report(&self, echo_metrics: &EchoServerMetrics, stats_history: &[StatsSample]) -> BenchmarkReport {
+        let elapsed = self.start_time.elapsed().as_secs_f64();
+        let task_spawns = self.task_spawns.load(Ordering::Relaxed);
+        let task_spawn_times = self.task_spawn_times.load(Ordering::Relaxed);
+        let async_ops = self.async_operations.load(Ordering::Relaxed);
+        let async_op_times = self.async_operation_times.load(Ordering::Relaxed);
+        let latency = self.async_operation_latencies.summary();
+
+        let snapshots = self.memory_snapshots.read().await;
+        let (min_memory_mb, max_memory_mb, avg_memory_mb) = if snapshots.is_empty() {
+            (0.0, 0.0, 0.0)
+        } else {
+            let min = *snapshots.iter().min().unwrap() as f64 / 1024.0;
+            let max = *snapshots.iter().max().unwrap() as f64 / 1024.0;
+            let avg = snapshots.iter().sum::<u64>() as f64 / snapshots.len() as f64 / 1024.0;
+            (min, max, avg)
+        };
+
+        let bytes_transferred = echo_metrics.bytes_transferred.load(Ordering::Relaxed);
+        let datagrams_sent = self.datagrams_sent.load(Ordering::Relaxed);
+        let datagrams_lost = self.datagrams_lost.load(Ordering::Relaxed);
+
+        let peak_connections_per_sec = stats_history
+            .iter()
+            .map(|s| s.connections_per_sec)
+            .fold(0.0, f64::max);
+        let peak_messages_per_sec = stats_history
+            .iter()
+            .map(|s| s.messages_per_sec)
+            .fold(0.0, f64::max);
+        let peak_mb_per_sec = stats_history.iter().map(|s| s.mb_per_sec).fold(0.0, f64::max);
+
+        BenchmarkReport {
+            execution_time_secs: elapsed,
+            task_spawns,
+            avg_task_spawn_us: if task_spawns > 0 {
+                task_spawn_times as f64 / task_spawns as f64 / 1000.0
+            } else {
+                0.0
+            },
+            async_operations: async_ops,
+            avg_async_operation_us: if async_ops > 0 {
+                async_op_times as f64 / async_ops as f64 / 1000.0
+            } else {
+                0.0
+            },
+            p50_us: latency.p50_ns as f64 / 1000.0,
+            p90_us: latency.p90_ns as f64 / 1000.0,
+            p95_us: latency.p95_ns as f64 / 1000.0,
+            p99_us: latency.p99_ns as f64 / 1000.0,
+            max_us: latency.max_ns as f64 / 1000.0,
+            connections_accepted: echo_metrics.connections_accepted.load(Ordering::Relaxed),
+            peak_connections: echo_metrics.peak_connections.load(Ordering::Relaxed),
+            messages_echoed: echo_metrics.messages_echoed.load(Ordering::Relaxed),
+            bytes_transferred,
+            throughput_mb_per_sec: bytes_transferred as f64 / (1024.0 * 1024.0) / elapsed.max(1e-9),
+            min_memory_mb,
+            max_memory_mb,
+            avg_memory_mb,
+            memory_growth_mb: max_memory_mb - min_memory_mb,
+            datagrams_sent,
+            datagrams_lost,
+            datagram_loss_pct: self.datagram_loss_pct(),
+            peak_connections_per_sec,
+            peak_messages_per_sec,
+            peak_mb_per_sec,
+        }
+    }
+
     async fn print_metrics(&self, test_name: &str) {
         let elapsed = self.start_time.elapsed();
         let task_spawns = self.task_spawns.load(Ordering::Relaxed);
@@ -99,11 +272,20 @@ impl AsyncMetrics {
         if async_ops > 0 {
             println!("\nASYNC OPERATIONS:");
             println!("  Total operations: {}", async_ops);
-            println!("  Avg operation time: {:.2} μs", 
+            println!("  Avg operation time: {:.2} μs",
                      async_op_times as f64 / async_ops as f64 / 1000.0);
             println!("  Operations per second: {:.2}", async_ops as f64 / elapsed.as_secs_f64());
+            self.async_operation_latencies.summary().print("ASYNC OPERATION");
         }
-        
+
+        let datagrams_sent = self.datagrams_sent.load(Ordering::Relaxed);
+        if datagrams_sent > 0 {
+            println!("\nUDP DATAGRAM LOSS:");
+            println!("  Sent: {}", datagrams_sent);
+            println!("  Lost: {}", self.datagrams_lost.load(Ordering::Relaxed));
+            println!("  Loss rate: {:.2}%", self.datagram_loss_pct());
+        }
+
         let snapshots = self.memory_snapshots.read().await;
         if !snapshots.is_empty() {
             let min_mem = *snapshots.iter().min().unwrap() as f64 / 1024.0;
@@ -125,6 +307,7 @@ struct EchoServerMetrics {
     messages_echoed: AtomicUsize,
     bytes_transferred: AtomicU64,
     connection_durations: AtomicU64, // microseconds
+    connection_duration_latencies: LatencyHistogram,
     active_connections: AtomicUsize,
     peak_connections: AtomicUsize,
     start_time: Instant,
@@ -137,6 +320,7 @@ impl EchoServerMetrics {
             messages_echoed: AtomicUsize::new(0),
             bytes_transferred: AtomicU64::new(0),
             connection_durations: AtomicU64::new(0),
+            connection_duration_latencies: LatencyHistogram::new(),
             active_connections: AtomicUsize::new(0),
             peak_connections: AtomicUsize::new(0),
             start_time: Instant::now(),
@@ -166,6 +350,7 @@ impl EchoServerMetrics {
         self.messages_echoed.fetch_add(messages, Ordering::Relaxed);
         self.bytes_transferred.fetch_add(bytes, Ordering::Relaxed);
         self.connection_durations.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        self.connection_duration_latencies.record(duration.as_nanos() as u64);
     }
     
     fn print_metrics(&self) {
@@ -188,8 +373,9 @@ impl EchoServerMetrics {
         println!("  Rate: {:.2} conn/s", connections as f64 / elapsed.as_secs_f64());
         if connections > 0 {
             println!("  Avg duration: {:.2} ms", total_duration as f64 / connections as f64 / 1000.0);
+            self.connection_duration_latencies.summary().print("CONNECTION DURATION");
         }
-        
+
         println!("\nTHROUGHPUT:");
         println!("  Messages: {}", messages);
         println!("  Messages/s: {:.2}", messages as f64 / elapsed.as_secs_f64());
@@ -246,14 +432,14 @@ async fn handle_echo_client(
 }
 
 async fn run_echo_server(
-    addr: &str, 
+    addr: &str,
     max_connections: usize,
+    metrics: Arc<EchoServerMetrics>,
     shutdown_rx: &mut broadcast::Receiver<()>
-) -> Arc<EchoServerMetrics> {
+) {
     println!("Starting echo server on {}", addr);
-    
+
     let listener = TcpListener::bind(addr).await.expect("Failed to bind");
-    let metrics = Arc::new(EchoServerMetrics::new());
     let semaphore = Arc::new(Semaphore::new(max_connections));
     
     println!("Echo server listening on {} (max {} connections)", addr, max_connections);
@@ -288,74 +474,161 @@ async fn run_echo_server(
             }
         }
     }
-    
-    metrics
+}
+
+async fn run_echo_server_udp(
+    addr: &str,
+    metrics: Arc<EchoServerMetrics>,
+    shutdown_rx: &mut broadcast::Receiver<()>
+) {
+    println!("Starting UDP echo server on {}", addr);
+
+    let socket = UdpSocket::bind(addr).await.expect("Failed to bind UDP socket");
+    let mut buffer = [0u8; 65536];
+
+    println!("UDP echo server listening on {}", addr);
+
+    loop {
+        tokio::select! {
+            // Handle incoming datagrams
+            recv_result = socket.recv_from(&mut buffer) => {
+                match recv_result {
+                    Ok((n, sender)) => {
+                        if socket.send_to(&buffer[0..n], sender).await.is_err() {
+                            eprintln!("Failed to echo datagram to {}", sender);
+                            continue;
+                        }
+                        metrics.messages_echoed.fetch_add(1, Ordering::Relaxed);
+                        metrics.bytes_transferred.fetch_add((n * 2) as u64, Ordering::Relaxed);
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to receive datagram: {}", e);
+                    }
+                }
+            }
+
+            // Handle shutdown signal
+            _ = shutdown_rx.recv() => {
+                println!("UDP echo server shutting down...");
+                break;
+            }
+        }
+    }
+}
+
+/// Builds the message for `msg_id` from client `client_id`, either a
+/// deterministic random payload of `message_size_kb` KB or a small default
+/// message when no size is requested.
+fn generate_payload(rng: &mut ChaCha8Rng, message_size_kb: usize, client_id: usize, msg_id: usize) -> Vec<u8> {
+    if message_size_kb > 0 {
+        let size = message_size_kb * 1024;
+        let mut payload = Vec::with_capacity(size);
+        // Generate payload in chunks to avoid large allocations
+        for _ in 0..(size / 64) {
+            payload.extend_from_slice(Alphanumeric.sample_string(rng, 64).as_bytes());
+        }
+        // Add remaining bytes
+        let remaining = size % 64;
+        if remaining > 0 {
+            payload.extend_from_slice(Alphanumeric.sample_string(rng, remaining).as_bytes());
+        }
+        payload
+    } else {
+        format!("Client-{}-Message-{}", client_id, msg_id).into_bytes()
+    }
 }
 
 async fn echo_client_benchmark(
-    server_addr: &str, 
-    num_clients: usize, 
+    server_addr: &str,
+    num_clients: usize,
     messages_per_client: usize,
     message_size_kb: usize,
+    seed: u64,
+    transport: Transport,
     metrics: Arc<AsyncMetrics>
 ) {
     println!("\nECHO CLIENT BENCHMARK");
-    println!("Clients: {}, Messages per client: {}, Message size: {} KB", 
+    println!("Clients: {}, Messages per client: {}, Message size: {} KB",
              num_clients, messages_per_client, if message_size_kb > 0 { message_size_kb } else { 0 });
-    
+
     let mut handles = vec![];
-    
+
     for client_id in 0..num_clients {
         let addr = server_addr.to_string();
         let metrics_clone = Arc::clone(&metrics);
-        
+        // Derive a per-client sub-stream so parallel clients stay
+        // reproducible regardless of task spawn/scheduling order.
+        let client_seed = seed ^ (client_id as u64);
+
         let spawn_start = Instant::now();
         let handle = tokio::spawn(async move {
             metrics_clone.record_task_spawn(spawn_start.elapsed());
-            
-            if let Ok(mut stream) = TcpStream::connect(&addr).await {
-                for msg_id in 0..messages_per_client {
-                    let message: Vec<u8> = if message_size_kb > 0 {
-                        // Generate random payload of specified size
-                        let size = message_size_kb * 1024;
-                        let mut rng = thread_rng();
-                        let mut payload = Vec::with_capacity(size);
-                        // Generate payload in chunks to avoid large allocations
-                        for _ in 0..(size / 64) {
-                            payload.extend_from_slice(Alphanumeric.sample_string(&mut rng, 64).as_bytes());
-                        }
-                        // Add remaining bytes
-                        let remaining = size % 64;
-                        if remaining > 0 {
-                            payload.extend_from_slice(Alphanumeric.sample_string(&mut rng, remaining).as_bytes());
+            let mut rng = ChaCha8Rng::seed_from_u64(client_seed);
+
+            match transport {
+                Transport::Tcp => {
+                    if let Ok(mut stream) = TcpStream::connect(&addr).await {
+                        for msg_id in 0..messages_per_client {
+                            let message = generate_payload(&mut rng, message_size_kb, client_id, msg_id);
+
+                            let op_start = Instant::now();
+
+                            // Send message
+                            if stream.write_all(&message).await.is_err() {
+                                break;
+                            }
+
+                            // Read echo
+                            let mut buffer = vec![0; message.len()];
+                            if stream.read_exact(&mut buffer).await.is_err() {
+                                break;
+                            }
+
+                            metrics_clone.record_async_operation(op_start.elapsed());
+
+                            // Small delay to simulate realistic usage
+                            sleep(Duration::from_millis(1)).await;
                         }
-                        payload
+                        println!("Client {} finished", client_id);
                     } else {
-                        // Use default small message
-                        format!("Client-{}-Message-{}", client_id, msg_id).into_bytes()
-                    };
-                    
-                    let op_start = Instant::now();
-                    
-                    // Send message
-                    if stream.write_all(&message).await.is_err() {
-                        break;
+                        eprintln!("Client {} failed to connect", client_id);
                     }
-                    
-                    // Read echo
-                    let mut buffer = vec![0; message.len()];
-                    if stream.read_exact(&mut buffer).await.is_err() {
-                        break;
+                }
+                Transport::Udp => {
+                    match UdpSocket::bind("0.0.0.0:0").await {
+                        Ok(socket) => {
+                            if socket.connect(&addr).await.is_err() {
+                                eprintln!("Client {} failed to connect (UDP)", client_id);
+                            } else {
+                                for msg_id in 0..messages_per_client {
+                                    let message = generate_payload(&mut rng, message_size_kb, client_id, msg_id);
+
+                                    let op_start = Instant::now();
+                                    metrics_clone.record_datagram_sent();
+
+                                    if socket.send(&message).await.is_err() {
+                                        metrics_clone.record_datagram_lost();
+                                        continue;
+                                    }
+
+                                    // UDP can silently drop either the request or the
+                                    // echo, so a missing/timed-out reply counts as lost
+                                    // rather than a hard error.
+                                    let mut buffer = vec![0u8; message.len()];
+                                    match timeout(Duration::from_secs(2), socket.recv(&mut buffer)).await {
+                                        Ok(Ok(_)) => metrics_clone.record_async_operation(op_start.elapsed()),
+                                        _ => metrics_clone.record_datagram_lost(),
+                                    }
+
+                                    // Small delay to simulate realistic usage
+                                    sleep(Duration::from_millis(1)).await;
+                                }
+                                println!("Client {} finished", client_id);
+                            }
+                        }
+                        Err(e) => eprintln!("Client {} failed to bind UDP socket: {}", client_id, e),
                     }
-                    
-                    metrics_clone.record_async_operation(op_start.elapsed());
-                    
-                    // Small delay to simulate realistic usage
-                    sleep(Duration::from_millis(1)).await;
                 }
-                println!("Client {} finished", client_id);
-            } else {
-                eprintln!("Client {} failed to connect", client_id);
             }
         });
         metrics.record_task_spawn(spawn_start.elapsed());
@@ -373,12 +646,13 @@ async fn comprehensive_tokio_benchmark(args: Args, num_threads: usize) {
     println!("{:=<80}", "");
     
     let metrics = Arc::new(AsyncMetrics::new());
-    
+
     // Get system info
     let cores = std::thread::available_parallelism()
         .map(|n| n.get())
         .unwrap_or(4);
-    
+
+    let seed = args.seed.unwrap_or_else(|| thread_rng().next_u64());
     println!("System cores: {}", cores);
     println!("Tokio worker threads: {}", num_threads);
     println!("Tokio version: {}", env!("CARGO_PKG_VERSION"));
@@ -387,55 +661,170 @@ async fn comprehensive_tokio_benchmark(args: Args, num_threads: usize) {
     println!("  - Messages per client: {}", args.messages_per_client);
     println!("  - Max connections: {}", args.max_connections);
     println!("  - Message size: {} KB", if args.message_size_kb > 0 { args.message_size_kb } else { 0 });
-    
+    println!("  - Seed: {} (pass --seed {} to replay this exact run)", seed, seed);
+    println!("  - Transport: {:?}", args.transport);
+
+
     // Print initial memory
     if let Some(mem) = get_current_memory_usage() {
         println!("Initial memory: {:.2} MB", mem as f64 / 1024.0);
     }
-    
+
+    if args.flamegraph.is_some() {
+        #[cfg(feature = "profiling")]
+        println!("Profiling enabled at {} Hz, writing to {}", args.flamegraph_hz, args.flamegraph.as_ref().unwrap());
+        #[cfg(not(feature = "profiling"))]
+        eprintln!(
+            "--flamegraph was set but this binary was built without the `profiling` feature; no flamegraph will be written"
+        );
+    }
+    #[cfg(feature = "profiling")]
+    let profiler_guard = args.flamegraph.as_ref().map(|_| start_profiler(args.flamegraph_hz));
+
     // Echo server benchmark
     let (shutdown_tx, mut shutdown_rx) = broadcast::channel(1);
-    
+
     let server_addr = "127.0.0.1:9999";
     let server_metrics = Arc::new(AsyncMetrics::new());
-    
+    let echo_metrics = Arc::new(EchoServerMetrics::new());
+
     // Start server
     let server_handle = {
         let mut shutdown_rx = shutdown_tx.subscribe();
         let max_connections = args.max_connections;
+        let transport = args.transport;
+        let echo_metrics = Arc::clone(&echo_metrics);
         tokio::spawn(async move {
-            run_echo_server(server_addr, max_connections, &mut shutdown_rx).await
+            match transport {
+                Transport::Tcp => run_echo_server(server_addr, max_connections, echo_metrics, &mut shutdown_rx).await,
+                Transport::Udp => run_echo_server_udp(server_addr, echo_metrics, &mut shutdown_rx).await,
+            }
         })
     };
-    
+
     // Give server time to start
     sleep(Duration::from_millis(100)).await;
-    
+
+    // Background sampler: every --stats-interval-ms, print a delta line
+    // against the previous tick so ramp-up/plateau/growth are visible while
+    // the run is still in flight, not just in the final summary.
+    let stats_history = Arc::new(RwLock::new(Vec::<StatsSample>::new()));
+    let stats_handle = if args.stats_interval_ms > 0 {
+        let mut shutdown_rx = shutdown_tx.subscribe();
+        let echo_metrics = Arc::clone(&echo_metrics);
+        let server_metrics = Arc::clone(&server_metrics);
+        let stats_history = Arc::clone(&stats_history);
+        let interval = Duration::from_millis(args.stats_interval_ms);
+        let start_time = Instant::now();
+        Some(tokio::spawn(async move {
+            let mut prev = StatsSample {
+                elapsed_secs: 0.0,
+                connections_per_sec: 0.0,
+                messages_per_sec: 0.0,
+                mb_per_sec: 0.0,
+                active_connections: 0,
+                memory_mb: 0.0,
+            };
+            let mut prev_connections = 0usize;
+            let mut prev_messages = 0usize;
+            let mut prev_bytes = 0u64;
+
+            loop {
+                tokio::select! {
+                    _ = sleep(interval) => {
+                        let connections = echo_metrics.connections_accepted.load(Ordering::Relaxed);
+                        let messages = echo_metrics.messages_echoed.load(Ordering::Relaxed);
+                        let bytes = echo_metrics.bytes_transferred.load(Ordering::Relaxed);
+                        let active_connections = echo_metrics.active_connections.load(Ordering::Relaxed);
+                        let elapsed_secs = start_time.elapsed().as_secs_f64();
+                        let dt = (elapsed_secs - prev.elapsed_secs).max(1e-9);
+
+                        // Also feeds the end-of-run min/max/avg memory numbers.
+                        server_metrics.take_memory_snapshot().await;
+                        let memory_mb = get_current_memory_usage().map(|kb| kb as f64 / 1024.0).unwrap_or(0.0);
+
+                        let sample = StatsSample {
+                            elapsed_secs,
+                            connections_per_sec: (connections - prev_connections) as f64 / dt,
+                            messages_per_sec: (messages - prev_messages) as f64 / dt,
+                            mb_per_sec: (bytes - prev_bytes) as f64 / (1024.0 * 1024.0) / dt,
+                            active_connections,
+                            memory_mb,
+                        };
+
+                        println!(
+                            "[stats {:>7.1}s] conn/s: {:>8.2}  msg/s: {:>10.2}  MB/s: {:>8.2}  active: {:>5}  mem: {:>8.2} MB",
+                            sample.elapsed_secs, sample.connections_per_sec, sample.messages_per_sec,
+                            sample.mb_per_sec, sample.active_connections, sample.memory_mb
+                        );
+
+                        stats_history.write().await.push(sample);
+                        prev = sample;
+                        prev_connections = connections;
+                        prev_messages = messages;
+                        prev_bytes = bytes;
+                    }
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        }))
+    } else {
+        None
+    };
+
     // Run client benchmark
     echo_client_benchmark(
-        server_addr, 
-        args.num_clients, 
-        args.messages_per_client, 
+        server_addr,
+        args.num_clients,
+        args.messages_per_client,
         args.message_size_kb,
+        seed,
+        args.transport,
         Arc::clone(&server_metrics)
     ).await;
-    
-    // Shutdown server
+
+    // Shutdown server and sampler
     let _ = shutdown_tx.send(());
-    if let Ok(echo_metrics) = server_handle.await {
-        echo_metrics.print_metrics();
+    let _ = server_handle.await;
+    if let Some(stats_handle) = stats_handle {
+        let _ = stats_handle.await;
     }
-    
-    server_metrics.print_metrics("Echo Server Client").await;
-    
-    // Print final memory
-    if let Some(mem) = get_current_memory_usage() {
-        println!("\nFinal memory: {:.2} MB", mem as f64 / 1024.0);
+
+    // Stop profiling after the accept loop and echo handlers have finished,
+    // so the flamegraph covers both hot paths.
+    #[cfg(feature = "profiling")]
+    if let (Some(path), Some(guard)) = (&args.flamegraph, profiler_guard) {
+        write_flamegraph(guard, path);
+    }
+
+    let stats_history = stats_history.read().await;
+
+    match args.output_format {
+        OutputFormat::Text => {
+            echo_metrics.print_metrics();
+            server_metrics.print_metrics("Echo Server Client").await;
+
+            if let Some(peak) = stats_history.iter().max_by(|a, b| a.mb_per_sec.total_cmp(&b.mb_per_sec)) {
+                println!("\nPEAK INSTANTANEOUS THROUGHPUT:");
+                println!("  {:.2} MB/s (at {:.1}s)", peak.mb_per_sec, peak.elapsed_secs);
+            }
+            if let Some(peak) = stats_history.iter().max_by(|a, b| a.connections_per_sec.total_cmp(&b.connections_per_sec)) {
+                println!("  {:.2} conn/s (at {:.1}s)", peak.connections_per_sec, peak.elapsed_secs);
+            }
+
+            if let Some(mem) = get_current_memory_usage() {
+                println!("\nFinal memory: {:.2} MB", mem as f64 / 1024.0);
+            }
+
+            println!("\n{:=<80}", "");
+            println!("ECHO SERVER BENCHMARK COMPLETED");
+            println!("{:=<80}", "");
+        }
+        format @ (OutputFormat::Json | OutputFormat::Csv) => {
+            let report = server_metrics.build_report(&echo_metrics, &stats_history).await;
+            report.print(format);
+        }
     }
-    
-    println!("\n{:=<80}", "");
-    println!("ECHO SERVER BENCHMARK COMPLETED");
-    println!("{:=<80}", "");
 }
 
 fn main() {
@@ -0,0 +1,111 @@
+// Structured (JSON/CSV) rendering of a completed benchmark run, so results
+// can be piped into dashboards or diffed across CI runs instead of only
+// being readable as the `print_metrics` text dump.
+
+use clap::ValueEnum;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BenchmarkReport {
+    pub execution_time_secs: f64,
+
+    pub task_spawns: usize,
+    pub avg_task_spawn_us: f64,
+
+    pub async_operations: usize,
+    pub avg_async_operation_us: f64,
+    pub p50_us: f64,
+    pub p90_us: f64,
+    pub p95_us: f64,
+    pub p99_us: f64,
+    pub max_us: f64,
+
+    pub connections_accepted: usize,
+    pub peak_connections: usize,
+    pub messages_echoed: usize,
+    pub bytes_transferred: u64,
+    pub throughput_mb_per_sec: f64,
+
+    pub min_memory_mb: f64,
+    pub max_memory_mb: f64,
+    pub avg_memory_mb: f64,
+    pub memory_growth_mb: f64,
+
+    /// Always 0 for TCP; only the UDP client path records sends/losses.
+    pub datagrams_sent: usize,
+    pub datagrams_lost: usize,
+    pub datagram_loss_pct: f64,
+
+    /// Best single sample from the live stats sampler, as opposed to the
+    /// run-averaged throughput fields above.
+    pub peak_connections_per_sec: f64,
+    pub peak_messages_per_sec: f64,
+    pub peak_mb_per_sec: f64,
+}
+
+impl BenchmarkReport {
+    pub fn print(&self, format: OutputFormat) {
+        match format {
+            OutputFormat::Text => { /* text output is handled by print_metrics callers */ }
+            OutputFormat::Json => println!("{}", self.to_json()),
+            OutputFormat::Csv => {
+                println!("{}", self.csv_header());
+                println!("{}", self.to_csv_row());
+            }
+        }
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            "{{\n  \"execution_time_secs\": {:.6},\n  \"task_spawns\": {},\n  \"avg_task_spawn_us\": {:.3},\n  \"async_operations\": {},\n  \"avg_async_operation_us\": {:.3},\n  \"latency_us\": {{ \"p50\": {:.3}, \"p90\": {:.3}, \"p95\": {:.3}, \"p99\": {:.3}, \"max\": {:.3} }},\n  \"connections_accepted\": {},\n  \"peak_connections\": {},\n  \"messages_echoed\": {},\n  \"bytes_transferred\": {},\n  \"throughput_mb_per_sec\": {:.3},\n  \"memory_mb\": {{ \"min\": {:.3}, \"max\": {:.3}, \"avg\": {:.3}, \"growth\": {:.3} }},\n  \"datagrams\": {{ \"sent\": {}, \"lost\": {}, \"loss_pct\": {:.3} }},\n  \"peak\": {{ \"connections_per_sec\": {:.3}, \"messages_per_sec\": {:.3}, \"mb_per_sec\": {:.3} }}\n}}",
+            self.execution_time_secs,
+            self.task_spawns,
+            self.avg_task_spawn_us,
+            self.async_operations,
+            self.avg_async_operation_us,
+            self.p50_us, self.p90_us, self.p95_us, self.p99_us, self.max_us,
+            self.connections_accepted,
+            self.peak_connections,
+            self.messages_echoed,
+            self.bytes_transferred,
+            self.throughput_mb_per_sec,
+            self.min_memory_mb, self.max_memory_mb, self.avg_memory_mb, self.memory_growth_mb,
+            self.datagrams_sent, self.datagrams_lost, self.datagram_loss_pct,
+            self.peak_connections_per_sec, self.peak_messages_per_sec, self.peak_mb_per_sec,
+        )
+    }
+
+    fn csv_header(&self) -> &'static str {
+        "execution_time_secs,task_spawns,avg_task_spawn_us,async_operations,avg_async_operation_us,\
+p50_us,p90_us,p95_us,p99_us,max_us,connections_accepted,peak_connections,messages_echoed,\
+bytes_transferred,throughput_mb_per_sec,min_memory_mb,max_memory_mb,avg_memory_mb,memory_growth_mb,\
+datagrams_sent,datagrams_lost,datagram_loss_pct,\
+peak_connections_per_sec,peak_messages_per_sec,peak_mb_per_sec"
+    }
+
+    fn to_csv_row(&self) -> String {
+        format!(
+            "{:.6},{},{:.3},{},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{},{},{},{},{:.3},{:.3},{:.3},{:.3},{:.3},{},{},{:.3},{:.3},{:.3},{:.3}",
+            self.execution_time_secs,
+            self.task_spawns,
+            self.avg_task_spawn_us,
+            self.async_operations,
+            self.avg_async_operation_us,
+            self.p50_us, self.p90_us, self.p95_us, self.p99_us, self.max_us,
+            self.connections_accepted,
+            self.peak_connections,
+            self.messages_echoed,
+            self.bytes_transferred,
+            self.throughput_mb_per_sec,
+            self.min_memory_mb, self.max_memory_mb, self.avg_memory_mb, self.memory_growth_mb,
+            self.datagrams_sent, self.datagrams_lost, self.datagram_loss_pct,
+            self.peak_connections_per_sec, self.peak_messages_per_sec, self.peak_mb_per_sec,
+        )
+    }
+}
@@ -6,11 +6,246 @@ use std::collections::{VecDeque, hash_map::DefaultHasher};
 use std::hash::{Hash, Hasher};
 use std::env;
 use std::fs;
+use sysinfo::{Pid, System};
+use serde::{Deserialize, Serialize};
+
+/// Unified memory-sampling source: one implementation backs both the
+/// one-shot `MemoryStats` snapshots and `SystemMonitorService`'s tight
+/// sampling loop, so neither forks a `ps`/`tasklist` subprocess per read and
+/// platform coverage isn't limited to whatever branch `measure_current` used
+/// to special-case.
+trait MemoryProbe: Send + Sync {
+    fn current_rss_kb(&self) -> Option<u64>;
+    fn virtual_kb(&self) -> Option<u64>;
+}
+
+/// `MemoryProbe` backed by `sysinfo::System`, refreshed for this process's
+/// PID on every read. `System` needs `&mut self` to refresh but the trait
+/// exposes read-only queries, so the refresh is serialized behind a `Mutex`
+/// (this file's usual tool for interior mutability, rather than `RefCell`,
+/// since probes are shared across the monitor thread and the caller).
+struct SysinfoProbe {
+    system: Mutex<System>,
+    pid: Pid,
+}
+
+impl SysinfoProbe {
+    fn new() -> Self {
+        let pid = Pid::from_u32(std::process::id());
+        let mut system = System::new();
+        system.refresh_process(pid);
+        Self {
+            system: Mutex::new(system),
+            pid,
+        }
+    }
+}
+
+impl MemoryProbe for SysinfoProbe {
+    fn current_rss_kb(&self) -> Option<u64> {
+        let mut system = self.system.lock().unwrap();
+        system.refresh_process(self.pid);
+        system.process(self.pid).map(|p| p.memory())
+    }
+
+    fn virtual_kb(&self) -> Option<u64> {
+        let mut system = self.system.lock().unwrap();
+        system.refresh_process(self.pid);
+        system.process(self.pid).map(|p| p.virtual_memory())
+    }
+}
+
+/// Total physical memory (KB) and logical core count, read once for the
+/// PLATFORM banner -- sourced from `sysinfo` alongside `get_os_info()`/
+/// `get_cpu_architecture()` so that block is accurate on every OS instead of
+/// just reporting `target_os`/`target_arch`.
+fn system_overview() -> (u64, usize) {
+    let mut system = System::new();
+    system.refresh_memory();
+    system.refresh_cpu();
+    (system.total_memory(), system.cpus().len())
+}
+
+/// Machine-relative baseline so throughput numbers like `mutex_ops_per_sec`
+/// can be normalized across different hardware instead of compared raw.
+#[derive(Debug, Clone, Copy, Default)]
+struct HardwareScores {
+    cpu_ops_per_sec: f64,
+    memory_bandwidth_gib_per_sec: f64,
+    allocation_ops_per_sec: f64,
+}
+
+/// How long each of `calibrate`'s three measurements runs for. Short enough
+/// that calibration doesn't dominate total run time, long enough to average
+/// out scheduling noise.
+const CALIBRATION_BUDGET: Duration = Duration::from_millis(200);
+
+/// Runs the three calibration measurements described on `HardwareScores`
+/// and returns the resulting baseline. Called once, before the concurrency
+/// tests, so their numbers can be normalized against it.
+fn calibrate() -> HardwareScores {
+    HardwareScores {
+        cpu_ops_per_sec: calibrate_cpu(),
+        memory_bandwidth_gib_per_sec: calibrate_memory_bandwidth(),
+        allocation_ops_per_sec: calibrate_allocation(),
+    }
+}
+
+/// Single-thread CPU score: a tight integer/float loop run for
+/// `CALIBRATION_BUDGET`, reported as iterations/sec.
+fn calibrate_cpu() -> f64 {
+    let start = Instant::now();
+    let mut iterations: u64 = 0;
+    let mut acc: f64 = 1.0;
+
+    while start.elapsed() < CALIBRATION_BUDGET {
+        for _ in 0..10_000 {
+            acc = (acc * 1.0000001 + iterations as f64).sin();
+            iterations += 1;
+        }
+    }
+
+    std::hint::black_box(acc);
+    iterations as f64 / start.elapsed().as_secs_f64()
+}
+
+/// Memory-bandwidth score: repeatedly `memcpy`s a buffer larger than a
+/// typical L2 cache, reported as GiB/s.
+fn calibrate_memory_bandwidth() -> f64 {
+    const BUFFER_SIZE_BYTES: usize = 32 * 1024 * 1024;
+
+    let src = vec![0xABu8; BUFFER_SIZE_BYTES];
+    let mut dst = vec![0u8; BUFFER_SIZE_BYTES];
+
+    let start = Instant::now();
+    let mut bytes_copied: u64 = 0;
+
+    while start.elapsed() < CALIBRATION_BUDGET {
+        dst.copy_from_slice(&src);
+        bytes_copied += BUFFER_SIZE_BYTES as u64;
+    }
+
+    std::hint::black_box(&dst);
+    let gib_copied = bytes_copied as f64 / (1024.0 * 1024.0 * 1024.0);
+    gib_copied / start.elapsed().as_secs_f64()
+}
+
+/// Allocation score: allocates and drops `String`/`Vec` values sized like
+/// the producer-consumer tests' per-item data, reported as allocs/sec.
+fn calibrate_allocation() -> f64 {
+    let start = Instant::now();
+    let mut allocations: u64 = 0;
+
+    while start.elapsed() < CALIBRATION_BUDGET {
+        for _ in 0..1000 {
+            let s = String::from("producer-consumer-benchmark-item");
+            let v: Vec<i32> = vec![0; 16];
+            std::hint::black_box((&s, &v));
+            allocations += 2;
+        }
+    }
+
+    allocations as f64 / start.elapsed().as_secs_f64()
+}
+
+/// Linux-only `/proc/net/snmp` UDP counters (`Udp:` line), read before and
+/// after a UDP run so the delta shows what the kernel's UDP stack did with
+/// the traffic -- e.g. `RcvbufErrors` rising means the receive buffer, not
+/// the consumer code, is the bottleneck.
+#[derive(Debug, Clone, Copy, Default)]
+struct UdpNetStats {
+    in_datagrams: u64,
+    out_datagrams: u64,
+    in_errors: u64,
+    rcvbuf_errors: u64,
+}
+
+impl UdpNetStats {
+    #[cfg(target_os = "linux")]
+    fn snapshot() -> Option<Self> {
+        let contents = fs::read_to_string("/proc/net/snmp").ok()?;
+        let mut lines = contents.lines();
+        let mut header: Option<Vec<&str>> = None;
+        let mut values: Option<Vec<&str>> = None;
+
+        while let Some(line) = lines.next() {
+            if let Some(rest) = line.strip_prefix("Udp: ") {
+                if header.is_none() {
+                    header = Some(rest.split_whitespace().collect());
+                } else {
+                    values = Some(rest.split_whitespace().collect());
+                    break;
+                }
+            }
+        }
+
+        let header = header?;
+        let values = values?;
+        let field = |name: &str| -> u64 {
+            header
+                .iter()
+                .position(|h| *h == name)
+                .and_then(|idx| values.get(idx))
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(0)
+        };
+
+        Some(Self {
+            in_datagrams: field("InDatagrams"),
+            out_datagrams: field("OutDatagrams"),
+            in_errors: field("InErrors"),
+            rcvbuf_errors: field("RcvbufErrors"),
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn snapshot() -> Option<Self> {
+        None
+    }
+}
+
+/// Loopback-interface RX drop counter from `/proc/net/dev`'s `lo:` line
+/// (the fourth whitespace-separated RX field). Linux-only, same as
+/// `UdpNetStats`.
+#[cfg(target_os = "linux")]
+fn read_proc_net_dev_lo_drops() -> Option<u64> {
+    let contents = fs::read_to_string("/proc/net/dev").ok()?;
+    for line in contents.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("lo:") {
+            let fields: Vec<&str> = rest.split_whitespace().collect();
+            return fields.get(3).and_then(|v| v.parse::<u64>().ok());
+        }
+    }
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_proc_net_dev_lo_drops() -> Option<u64> {
+    None
+}
+
+/// Paired `/proc/net/snmp` + `/proc/net/dev` read, taken once before and
+/// once after a UDP run; `None` fields mean the platform doesn't have
+/// those proc files (anything but Linux).
+#[derive(Debug, Clone, Copy, Default)]
+struct NetStatsSnapshot {
+    udp: Option<UdpNetStats>,
+    lo_dropped: Option<u64>,
+}
+
+fn snapshot_net_stats() -> NetStatsSnapshot {
+    NetStatsSnapshot {
+        udp: UdpNetStats::snapshot(),
+        lo_dropped: read_proc_net_dev_lo_drops(),
+    }
+}
 
-#[derive(Debug)]
 struct MemoryStats {
+    probe: Box<dyn MemoryProbe>,
     peak_rss_kb: u64,
     current_rss_kb: u64,
+    peak_virtual_kb: u64,
     heap_size_estimated_kb: u64,
     thread_overhead_kb: u64,
 }
@@ -18,81 +253,25 @@ struct MemoryStats {
 impl MemoryStats {
     fn new() -> Self {
         Self {
+            probe: Box::new(SysinfoProbe::new()),
             peak_rss_kb: 0,
             current_rss_kb: 0,
+            peak_virtual_kb: 0,
             heap_size_estimated_kb: 0,
             thread_overhead_kb: 0,
         }
     }
-    
+
     fn measure_current(&mut self) {
-        if let Ok(status) = fs::read_to_string("/proc/self/status") {
-            for line in status.lines() {
-                if line.starts_with("VmRSS:") {
-                    if let Some(kb_str) = line.split_whitespace().nth(1) {
-                        if let Ok(kb) = kb_str.parse::<u64>() {
-                            self.current_rss_kb = kb;
-                            self.peak_rss_kb = self.peak_rss_kb.max(kb);
-                        }
-                    }
-                } else if line.starts_with("VmPeak:") {
-                    if let Some(kb_str) = line.split_whitespace().nth(1) {
-                        if let Ok(kb) = kb_str.parse::<u64>() {
-                            self.peak_rss_kb = self.peak_rss_kb.max(kb);
-                        }
-                    }
-                }
-            }
-        } else if cfg!(target_os = "macos") {
-            self.measure_macos();
-        } else if cfg!(target_os = "windows") {
-            // Only call measure_windows if we're on Windows
-            #[cfg(target_os = "windows")]
-            self.measure_windows();
+        if let Some(kb) = self.probe.current_rss_kb() {
+            self.current_rss_kb = kb;
+            self.peak_rss_kb = self.peak_rss_kb.max(kb);
         }
-    }
-    
-    #[cfg(target_os = "macos")]
-    fn measure_macos(&mut self) {
-        use std::process::Command;
-        
-        if let Ok(output) = Command::new("ps")
-            .args(&["-o", "rss=", "-p"])
-            .arg(std::process::id().to_string())
-            .output() {
-            if let Ok(rss_str) = String::from_utf8(output.stdout) {
-                if let Ok(rss_kb) = rss_str.trim().parse::<u64>() {
-                    self.current_rss_kb = rss_kb;
-                    self.peak_rss_kb = self.peak_rss_kb.max(rss_kb);
-                }
-            }
-        }
-    }
-    
-    #[cfg(target_os = "windows")]
-    fn measure_windows(&mut self) {
-        use std::process::Command;
-        
-        if let Ok(output) = Command::new("tasklist")
-            .args(&["/fi", &format!("PID eq {}", std::process::id()), "/fo", "csv"])
-            .output() {
-            if let Ok(output_str) = String::from_utf8(output.stdout) {
-                if let Some(line) = output_str.lines().nth(1) {
-                    let fields: Vec<&str> = line.split(',').collect();
-                    if fields.len() > 4 {
-                        let mem_str = fields[4].trim_matches('"').replace(",", "");
-                        if let Some(kb_str) = mem_str.strip_suffix(" K") {
-                            if let Ok(kb) = kb_str.parse::<u64>() {
-                                self.current_rss_kb = kb;
-                                self.peak_rss_kb = self.peak_rss_kb.max(kb);
-                            }
-                        }
-                    }
-                }
-            }
+        if let Some(kb) = self.probe.virtual_kb() {
+            self.peak_virtual_kb = self.peak_virtual_kb.max(kb);
         }
     }
-    
+
     fn estimate_heap_size(&mut self, data_structures: Vec<(&str, usize)>) {
         let mut total_estimated = 0;
         
@@ -116,6 +295,7 @@ impl MemoryStats {
         println!("\nMEMORY ANALYSIS: {}", test_name);
         println!("  Current RSS: {} KB ({:.1} MB)", self.current_rss_kb, self.current_rss_kb as f64 / 1024.0);
         println!("  Peak RSS: {} KB ({:.1} MB)", self.peak_rss_kb, self.peak_rss_kb as f64 / 1024.0);
+        println!("  Peak virtual memory: {} KB ({:.1} MB)", self.peak_virtual_kb, self.peak_virtual_kb as f64 / 1024.0);
         println!("  Estimated heap: {} KB ({:.1} MB)", self.heap_size_estimated_kb, self.heap_size_estimated_kb as f64 / 1024.0);
         println!("  Thread overhead: {} KB ({:.1} MB)", self.thread_overhead_kb, self.thread_overhead_kb as f64 / 1024.0);
         
@@ -128,7 +308,113 @@ impl MemoryStats {
     }
 }
 
-#[derive(Debug)]
+/// Samples RSS and CPU load on a fixed cadence for the whole run, instead of
+/// the one-shot `MemoryStats` snapshots taken around individual benchmarks --
+/// gives a time-series that shows how memory moves *during* a benchmark, not
+/// just before/after it.
+struct SystemMonitorService {
+    probe: Box<dyn MemoryProbe>,
+    samples: Mutex<Vec<(f64, u64, f64)>>,
+    shutdown: Arc<AtomicBool>,
+    start_time: Instant,
+    handle: Mutex<Option<thread::JoinHandle<()>>>,
+}
+
+impl SystemMonitorService {
+    const POLL_INTERVAL: Duration = Duration::from_millis(500);
+    const RSS_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+    const CPU_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+    fn start() -> Arc<Self> {
+        let service = Arc::new(Self {
+            probe: Box::new(SysinfoProbe::new()),
+            samples: Mutex::new(Vec::new()),
+            shutdown: Arc::new(AtomicBool::new(false)),
+            start_time: Instant::now(),
+            handle: Mutex::new(None),
+        });
+
+        let worker = Arc::clone(&service);
+        let handle = thread::spawn(move || worker.run());
+        *service.handle.lock().unwrap() = Some(handle);
+        service
+    }
+
+    fn run(&self) {
+        let mut last_rss_sample = Instant::now() - Self::RSS_SAMPLE_INTERVAL;
+        let mut last_cpu_sample = Instant::now() - Self::CPU_SAMPLE_INTERVAL;
+        let mut current_rss_kb = self.probe.current_rss_kb().unwrap_or(0);
+        let mut current_cpu_load = Self::sample_cpu_load();
+
+        while !self.shutdown.load(Ordering::Relaxed) {
+            let now = Instant::now();
+
+            if now.duration_since(last_rss_sample) >= Self::RSS_SAMPLE_INTERVAL {
+                current_rss_kb = self.probe.current_rss_kb().unwrap_or(current_rss_kb);
+                last_rss_sample = now;
+            }
+
+            if now.duration_since(last_cpu_sample) >= Self::CPU_SAMPLE_INTERVAL {
+                current_cpu_load = Self::sample_cpu_load();
+                last_cpu_sample = now;
+            }
+
+            let elapsed_secs = self.start_time.elapsed().as_secs_f64();
+            if let Ok(mut samples) = self.samples.lock() {
+                samples.push((elapsed_secs, current_rss_kb, current_cpu_load));
+            }
+
+            thread::sleep(Self::POLL_INTERVAL);
+        }
+    }
+
+    /// `sysinfo` doesn't expose a load-average query, so this stays on
+    /// `/proc/loadavg` directly -- it's read once per `CPU_SAMPLE_INTERVAL`
+    /// tick, not per poll, so it doesn't reintroduce the per-sample cost the
+    /// `MemoryProbe` switch was meant to remove.
+    #[cfg(target_os = "linux")]
+    fn sample_cpu_load() -> f64 {
+        if let Ok(loadavg) = fs::read_to_string("/proc/loadavg") {
+            if let Some(one_min) = loadavg.split_whitespace().next() {
+                if let Ok(load) = one_min.parse::<f64>() {
+                    return load;
+                }
+            }
+        }
+        0.0
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn sample_cpu_load() -> f64 {
+        0.0
+    }
+
+    /// Stops the background thread, joins it, and writes the accumulated
+    /// time-series to `path` as CSV (`t_sec,rss_kb,cpu_load`).
+    fn shutdown_and_dump(&self, path: &str) {
+        self.shutdown.store(true, Ordering::Relaxed);
+
+        if let Some(handle) = self.handle.lock().unwrap().take() {
+            let _ = handle.join();
+        }
+
+        let samples = self.samples.lock().unwrap();
+        let mut csv = String::from("t_sec,rss_kb,cpu_load\n");
+        for (t_sec, rss_kb, cpu_load) in samples.iter() {
+            csv.push_str(&format!("{:.3},{},{:.2}\n", t_sec, rss_kb, cpu_load));
+        }
+
+        match fs::write(path, csv) {
+            Ok(()) => println!(
+                "\nSYSTEM MONITOR: wrote {} samples to '{}'",
+                samples.len(),
+                path
+            ),
+            Err(e) => eprintln!("Warning: failed to write system monitor CSV '{}': {}", path, e),
+        }
+    }
+}
+
 struct ConcurrencyMetrics {
     mutex_operations: AtomicUsize,
     mutex_lock_times: AtomicU64,
@@ -308,16 +594,40 @@ impl<T> Clone for ThreadSafeQueue<T> {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 enum ProducerConsumerMode {
     Channel,
     Queue,
+    #[serde(rename = "async")]
+    AsyncTokio,
+    #[serde(rename = "udp")]
+    UdpLoopback,
 }
 
-fn producer_consumer_benchmark(mode: ProducerConsumerMode, num_producers: usize, num_consumers: usize, items_per_producer: usize) {
+/// The `[ratios]` table in the TOML config: the producer-percentage sweep
+/// `producer_consumer_ratio_test` walks, previously hard-coded as
+/// `[10, 20, ..., 90]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct RatioTestConfig {
+    producer_percentages: Vec<usize>,
+}
+
+impl Default for RatioTestConfig {
+    fn default() -> Self {
+        Self {
+            producer_percentages: vec![10, 20, 30, 40, 50, 60, 70, 80, 90],
+        }
+    }
+}
+
+fn producer_consumer_benchmark(mode: ProducerConsumerMode, num_producers: usize, num_consumers: usize, items_per_producer: usize, max_threads: usize) {
     match mode {
         ProducerConsumerMode::Channel => producer_consumer_channel_benchmark(num_producers, num_consumers, items_per_producer),
         ProducerConsumerMode::Queue => producer_consumer_queue_benchmark(num_producers, num_consumers, items_per_producer),
+        ProducerConsumerMode::AsyncTokio => producer_consumer_async_tokio_benchmark(num_producers, num_consumers, items_per_producer, max_threads),
+        ProducerConsumerMode::UdpLoopback => producer_consumer_udp_benchmark(num_producers, num_consumers, items_per_producer),
     }
 }
 
@@ -429,6 +739,251 @@ fn producer_consumer_channel_benchmark(num_producers: usize, num_consumers: usiz
     metrics.print_results("Producer-Consumer Channel");
 }
 
+/// Same workload and `ConcurrencyMetrics` accounting as
+/// `producer_consumer_channel_benchmark`, but producers/consumers run as
+/// Tokio tasks on a multi-threaded runtime instead of OS threads, and the
+/// transport is `tokio::sync::mpsc` instead of `std::sync::mpsc` -- lets
+/// the same CSV columns compare green-task scheduling overhead directly
+/// against native-thread contention.
+fn producer_consumer_async_tokio_benchmark(num_producers: usize, num_consumers: usize, items_per_producer: usize, max_threads: usize) {
+    println!("\nPRODUCER-CONSUMER ASYNC TOKIO BENCHMARK (RUST)");
+    println!("Producers: {}, Consumers: {}, Items per producer: {}",
+             num_producers, num_consumers, items_per_producer);
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(max_threads.max(1))
+        .enable_all()
+        .build()
+        .expect("failed to build Tokio runtime");
+
+    let metrics = Arc::new(ConcurrencyMetrics::new());
+    let total_threads = num_producers + num_consumers;
+    let total_expected_items = num_producers * items_per_producer;
+
+    let data_structures = vec![
+        ("tokio::mpsc::unbounded_channel", std::mem::size_of::<tokio::sync::mpsc::UnboundedSender<String>>() + std::mem::size_of::<tokio::sync::mpsc::UnboundedReceiver<String>>()),
+        ("Arc<tokio::sync::Mutex<Receiver>>", std::mem::size_of::<Arc<tokio::sync::Mutex<tokio::sync::mpsc::UnboundedReceiver<String>>>>()),
+        ("String buffers (estimated)", total_expected_items * 32),
+    ];
+    metrics.update_memory(data_structures, total_threads);
+
+    runtime.block_on(async {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+        let rx = Arc::new(tokio::sync::Mutex::new(rx));
+        let producers_done = Arc::new(AtomicBool::new(false));
+
+        let mut producer_tasks = Vec::new();
+        let mut consumer_tasks = Vec::new();
+
+        for i in 0..num_producers {
+            let tx = tx.clone();
+            let metrics_clone = Arc::clone(&metrics);
+
+            producer_tasks.push(tokio::spawn(async move {
+                for j in 0..items_per_producer {
+                    let start_send = Instant::now();
+                    if tx.send(format!("Producer-{}-Item-{}", i, j)).is_ok() {
+                        metrics_clone.increment_produced();
+                        metrics_clone.record_channel_operation(start_send.elapsed());
+                    }
+
+                    if j % 100 == 0 {
+                        tokio::time::sleep(Duration::from_micros(1)).await;
+                    }
+                }
+                println!("Tokio Producer {} finished", i);
+            }));
+        }
+
+        for i in 0..num_consumers {
+            let rx = Arc::clone(&rx);
+            let metrics_clone = Arc::clone(&metrics);
+            let producers_done_clone = Arc::clone(&producers_done);
+
+            consumer_tasks.push(tokio::spawn(async move {
+                let mut local_consumed = 0;
+                loop {
+                    let start_recv = Instant::now();
+                    let received = {
+                        let mut rx_guard = rx.lock().await;
+                        match rx_guard.try_recv() {
+                            Ok(_item) => {
+                                metrics_clone.record_channel_operation(start_recv.elapsed());
+                                metrics_clone.increment_consumed();
+                                local_consumed += 1;
+                                true
+                            }
+                            Err(tokio::sync::mpsc::error::TryRecvError::Empty) => {
+                                if producers_done_clone.load(Ordering::Relaxed) {
+                                    break;
+                                }
+                                false
+                            }
+                            Err(tokio::sync::mpsc::error::TryRecvError::Disconnected) => break,
+                        }
+                    };
+
+                    if !received {
+                        tokio::time::sleep(Duration::from_micros(10)).await;
+                    }
+                }
+                println!("Tokio Consumer {} finished, consumed {} items", i, local_consumed);
+            }));
+        }
+
+        drop(tx);
+
+        for task in producer_tasks {
+            task.await.unwrap();
+        }
+
+        producers_done.store(true, Ordering::Relaxed);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        for task in consumer_tasks {
+            task.await.unwrap();
+        }
+    });
+
+    let final_data_structures = vec![
+        ("Final channel state", 0),
+        ("Cleanup overhead", 1024),
+    ];
+    metrics.update_memory(final_data_structures, 1);
+
+    metrics.print_results("Producer-Consumer Async Tokio");
+}
+
+/// UDP-over-loopback transport: each consumer owns a socket bound to
+/// `127.0.0.1:0` (OS-assigned port); producers send datagrams directly to
+/// those addresses round-robin instead of going through an in-process
+/// channel or queue. Useful for seeing what kernel-level UDP overhead (as
+/// opposed to the benchmark's own synchronization) costs on this machine.
+fn producer_consumer_udp_benchmark(num_producers: usize, num_consumers: usize, items_per_producer: usize) {
+    println!("\nPRODUCER-CONSUMER UDP LOOPBACK BENCHMARK (RUST)");
+    println!("Producers: {}, Consumers: {}, Items per producer: {}",
+             num_producers, num_consumers, items_per_producer);
+
+    if cfg!(not(target_os = "linux")) {
+        println!("Note: /proc/net/snmp and /proc/net/dev are Linux-only; NET_STATS will report 0");
+    }
+
+    let metrics = Arc::new(ConcurrencyMetrics::new());
+    let producers_done = Arc::new(AtomicBool::new(false));
+
+    let consumer_sockets: Vec<std::net::UdpSocket> = (0..num_consumers)
+        .map(|_| {
+            let socket = std::net::UdpSocket::bind("127.0.0.1:0").expect("failed to bind UDP consumer socket");
+            socket.set_read_timeout(Some(Duration::from_millis(20))).expect("failed to set read timeout");
+            socket
+        })
+        .collect();
+    let consumer_addrs: Vec<std::net::SocketAddr> = consumer_sockets
+        .iter()
+        .map(|s| s.local_addr().expect("failed to read local_addr"))
+        .collect();
+
+    let total_threads = num_producers + num_consumers;
+    let total_expected_items = num_producers * items_per_producer;
+
+    let data_structures = vec![
+        ("UdpSocket (per consumer)", std::mem::size_of::<std::net::UdpSocket>() * num_consumers),
+        ("Arc<AtomicBool>", std::mem::size_of::<Arc<AtomicBool>>()),
+        ("Datagram payloads (estimated)", total_expected_items * 32),
+    ];
+    metrics.update_memory(data_structures, total_threads);
+
+    let before_stats = snapshot_net_stats();
+
+    let mut producer_handles = Vec::new();
+    let mut consumer_handles = Vec::new();
+
+    for i in 0..num_producers {
+        let metrics_clone = Arc::clone(&metrics);
+        let consumer_addrs = consumer_addrs.clone();
+
+        let handle = thread::spawn(move || {
+            let socket = std::net::UdpSocket::bind("127.0.0.1:0").expect("failed to bind UDP producer socket");
+            for j in 0..items_per_producer {
+                let payload = format!("Producer-{}-Item-{}", i, j);
+                let target = consumer_addrs[(i + j) % consumer_addrs.len()];
+                let start_send = Instant::now();
+                if socket.send_to(payload.as_bytes(), target).is_ok() {
+                    metrics_clone.increment_produced();
+                    metrics_clone.record_channel_operation(start_send.elapsed());
+                }
+
+                if j % 100 == 0 {
+                    thread::sleep(Duration::from_micros(1));
+                }
+            }
+            println!("UDP Producer {} finished", i);
+        });
+        producer_handles.push(handle);
+    }
+
+    for (i, socket) in consumer_sockets.into_iter().enumerate() {
+        let metrics_clone = Arc::clone(&metrics);
+        let producers_done_clone = Arc::clone(&producers_done);
+
+        let handle = thread::spawn(move || {
+            let mut buf = [0u8; 256];
+            let mut local_consumed = 0;
+            loop {
+                let start_recv = Instant::now();
+                match socket.recv(&mut buf) {
+                    Ok(_len) => {
+                        metrics_clone.record_channel_operation(start_recv.elapsed());
+                        metrics_clone.increment_consumed();
+                        local_consumed += 1;
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::TimedOut => {
+                        if producers_done_clone.load(Ordering::Relaxed) {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+            println!("UDP Consumer {} finished, consumed {} items", i, local_consumed);
+        });
+        consumer_handles.push(handle);
+    }
+
+    for handle in producer_handles {
+        handle.join().unwrap();
+    }
+
+    producers_done.store(true, Ordering::Relaxed);
+    thread::sleep(Duration::from_millis(50));
+
+    for handle in consumer_handles {
+        handle.join().unwrap();
+    }
+
+    let after_stats = snapshot_net_stats();
+
+    let dropped_datagrams = match (before_stats.lo_dropped, after_stats.lo_dropped) {
+        (Some(before), Some(after)) => after.saturating_sub(before),
+        _ => 0,
+    };
+    let rcvbuf_errors = match (before_stats.udp, after_stats.udp) {
+        (Some(before), Some(after)) => after.rcvbuf_errors.saturating_sub(before.rcvbuf_errors),
+        _ => 0,
+    };
+
+    let final_data_structures = vec![
+        ("Final socket state", 0),
+        ("Cleanup overhead", 1024),
+    ];
+    metrics.update_memory(final_data_structures, 1);
+
+    metrics.print_results("Producer-Consumer UDP Loopback");
+
+    println!("\nNET_STATS: Dropped_Datagrams,Rcvbuf_Errors");
+    println!("{},{}", dropped_datagrams, rcvbuf_errors);
+}
+
 fn producer_consumer_queue_benchmark(num_producers: usize, num_consumers: usize, items_per_producer: usize) {
     println!("\nPRODUCER-CONSUMER QUEUE BENCHMARK (RUST)");
     println!("Producers: {}, Consumers: {}, Items per producer: {}", 
@@ -592,9 +1147,9 @@ fn shared_data_mutex_benchmark(num_threads: usize, operations_per_thread: usize)
     metrics.print_results("Shared Data Mutex");
 }
 
-fn benchmark_csv_output(max_threads: usize, items_per_test: usize) {
+fn benchmark_csv_output(max_threads: usize, items_per_test: usize, scores: HardwareScores) {
     println!("\nCSV OUTPUT FOR ANALYSIS:");
-    println!("Threads,Execution_Time_Sec,Mutex_Ops_Per_Sec,Avg_Mutex_Time_Us,Peak_Memory_MB,RSS_Memory_MB,Efficiency_Percent");
+    println!("CPU_Score_Ops_Per_Sec,Mem_Bandwidth_GiB_Per_Sec,Alloc_Score_Ops_Per_Sec,Threads,Execution_Time_Sec,Mutex_Ops_Per_Sec,Avg_Mutex_Time_Us,Peak_Memory_MB,RSS_Memory_MB,Efficiency_Percent");
     
     for threads in 1..=max_threads {
         let metrics = Arc::new(ConcurrencyMetrics::new());
@@ -652,7 +1207,10 @@ fn benchmark_csv_output(max_threads: usize, items_per_test: usize) {
         let peak_memory_mb = metrics.get_peak_memory_mb();
         
         if let Ok(stats) = metrics.memory_stats.lock() {
-            println!("{},{:.3},{:.2},{:.2},{:.1},{:.1},{:.1}",
+            println!("{:.0},{:.2},{:.0},{},{:.3},{:.2},{:.2},{:.1},{:.1},{:.1}",
+                     scores.cpu_ops_per_sec,
+                     scores.memory_bandwidth_gib_per_sec,
+                     scores.allocation_ops_per_sec,
                      threads,
                      metrics.get_elapsed_seconds(),
                      metrics.get_mutex_ops_per_sec(),
@@ -666,7 +1224,8 @@ fn benchmark_csv_output(max_threads: usize, items_per_test: usize) {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 struct BenchmarkConfig {
     max_threads: usize,
     items_per_test: usize,
@@ -677,6 +1236,12 @@ struct BenchmarkConfig {
     run_csv_output: bool,
     run_producer_consumer_ratio_test: bool,
     producer_consumer_mode: ProducerConsumerMode,
+    run_system_monitor: bool,
+    system_monitor_output: String,
+    ratios: RatioTestConfig,
+    #[serde(skip)]
+    hardware_scores: HardwareScores,
+    #[serde(skip)]
     help: bool,
 }
 
@@ -692,16 +1257,63 @@ impl Default for BenchmarkConfig {
             run_csv_output: true,
             run_producer_consumer_ratio_test: false,
             producer_consumer_mode: ProducerConsumerMode::Channel,
+            run_system_monitor: true,
+            system_monitor_output: "memory_timeseries.csv".to_string(),
+            ratios: RatioTestConfig::default(),
+            hardware_scores: HardwareScores::default(),
             help: false,
         }
     }
 }
 
+/// Loads `path` as TOML into a `BenchmarkConfig`. If `path` doesn't exist
+/// yet, writes the default config there first so the user gets a template
+/// to edit instead of an error.
+fn load_or_init_config(path: &str) -> BenchmarkConfig {
+    match fs::read_to_string(path) {
+        Ok(contents) => match toml::from_str::<BenchmarkConfig>(&contents) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Warning: failed to parse config file '{}': {}, using defaults", path, e);
+                BenchmarkConfig::default()
+            }
+        },
+        Err(_) => {
+            let default_config = BenchmarkConfig::default();
+            match toml::to_string_pretty(&default_config) {
+                Ok(toml_str) => match fs::write(path, toml_str) {
+                    Ok(()) => println!("Wrote default config template to '{}'", path),
+                    Err(e) => eprintln!("Warning: failed to write default config to '{}': {}", path, e),
+                },
+                Err(e) => eprintln!("Warning: failed to serialize default config: {}", e),
+            }
+            default_config
+        }
+    }
+}
+
+/// `--config`/`-C` is resolved before the rest of the flags are applied, so
+/// a config file supplies the starting point and any other CLI flags in the
+/// same invocation override individual fields on top of it.
+fn find_config_path(args: &[String]) -> Option<String> {
+    let mut i = 1;
+    while i < args.len() {
+        if (args[i] == "--config" || args[i] == "-C") && i + 1 < args.len() {
+            return Some(args[i + 1].clone());
+        }
+        i += 1;
+    }
+    None
+}
+
 fn parse_args() -> BenchmarkConfig {
     let args: Vec<String> = env::args().collect();
-    let mut config = BenchmarkConfig::default();
+    let mut config = match find_config_path(&args) {
+        Some(path) => load_or_init_config(&path),
+        None => BenchmarkConfig::default(),
+    };
     let mut i = 1;
-    
+
     while i < args.len() {
         match args[i].as_str() {
             "--help" | "-h" => {
@@ -778,6 +1390,8 @@ fn parse_args() -> BenchmarkConfig {
                     match args[i + 1].as_str() {
                         "channel" => config.producer_consumer_mode = ProducerConsumerMode::Channel,
                         "queue" => config.producer_consumer_mode = ProducerConsumerMode::Queue,
+                        "async" => config.producer_consumer_mode = ProducerConsumerMode::AsyncTokio,
+                        "udp" => config.producer_consumer_mode = ProducerConsumerMode::UdpLoopback,
                         _ => {
                             eprintln!("Warning: Invalid mode '{}', using channel", args[i + 1]);
                         }
@@ -788,6 +1402,22 @@ fn parse_args() -> BenchmarkConfig {
             "--ratio-test" => {
                 config.run_producer_consumer_ratio_test = true;
             }
+            "--no-monitor" => {
+                config.run_system_monitor = false;
+            }
+            "--monitor-output" => {
+                if i + 1 < args.len() {
+                    config.system_monitor_output = args[i + 1].clone();
+                    i += 1;
+                }
+            }
+            "--config" | "-C" => {
+                // Already applied by `find_config_path`/`load_or_init_config`
+                // before this loop started; just skip over its value.
+                if i + 1 < args.len() {
+                    i += 1;
+                }
+            }
             _ => {
                 if args[i].parse::<usize>().is_ok() && i == 1 {
                     if let Ok(n) = args[i].parse::<usize>() {
@@ -822,13 +1452,17 @@ fn print_usage() {
     println!("  -h, --help                 Show this help message");
     println!("  -t, --threads <N>          Maximum number of threads (1-64, default: auto-detect)");
     println!("  -i, --items <N>            Number of items per test (1-1000000, default: 10000)");
-    println!("  -m, --mode <MODE>          Producer-consumer mode: channel|queue (default: channel)");
+    println!("  -m, --mode <MODE>          Producer-consumer mode: channel|queue|async|udp (default: channel)");
     println!("  --csv-threads <N>          Threads for CSV output (1-32, default: 8)");
     println!("  --csv-items <N>            Items for CSV output (1-100000, default: 1000)");
     println!("  --no-producer-consumer     Skip producer-consumer benchmark");
     println!("  --no-mutex                 Skip mutex benchmark");
     println!("  --no-csv                   Skip CSV output");
     println!("  --ratio-test              Test different producer-consumer ratios");
+    println!("  --no-monitor               Skip the background system-monitor time-series");
+    println!("  --monitor-output <PATH>    CSV path for the monitor time-series (default: memory_timeseries.csv)");
+    println!("  -C, --config <PATH>        Load settings from a TOML config file (CLI flags override it);");
+    println!("                             writes a default template there if the path doesn't exist");
     println!();
     println!("MEMORY ANALYSIS FEATURES:");
     println!("  - Real RSS memory measurement (Linux/macOS/Windows)");
@@ -859,13 +1493,13 @@ fn get_cpu_architecture() -> &'static str {
     else { "unknown" }
 }
 
-fn producer_consumer_ratio_test(mode: ProducerConsumerMode, total_threads: usize, items_per_producer: usize) {
+fn producer_consumer_ratio_test(mode: ProducerConsumerMode, total_threads: usize, items_per_producer: usize, producer_percentages: &[usize]) {
     println!("\nPRODUCER-CONSUMER RATIO TEST");
     println!("Testing different producer-consumer ratios with {:?} mode", mode);
     println!("Total threads: {}, Items per producer: {}", total_threads, items_per_producer);
     println!("\nProducers,Consumers,Total_Time_Sec,Messages_Per_Sec,Efficiency_Percent,Peak_Memory_MB");
     
-    for producer_pct in [10, 20, 30, 40, 50, 60, 70, 80, 90] {
+    for &producer_pct in producer_percentages {
         let num_producers = (total_threads * producer_pct / 100).max(1);
         let num_consumers = (total_threads - num_producers).max(1);
         
@@ -1022,12 +1656,182 @@ fn producer_consumer_ratio_test(mode: ProducerConsumerMode, total_threads: usize
                 producers_done.store(true, Ordering::Relaxed);
                 thread::sleep(Duration::from_millis(10));
                 
+                for handle in consumer_handles {
+                    handle.join().unwrap();
+                }
+            }
+            ProducerConsumerMode::AsyncTokio => {
+                let runtime = tokio::runtime::Builder::new_multi_thread()
+                    .worker_threads((num_producers + num_consumers).max(1))
+                    .enable_all()
+                    .build()
+                    .expect("failed to build Tokio runtime");
+
+                let data_structures = vec![
+                    ("ratio_test_async_tokio", 1024),
+                    ("estimated_messages", num_producers * items_per_producer * 32),
+                ];
+                metrics.update_memory(data_structures, num_producers + num_consumers);
+
+                runtime.block_on(async {
+                    let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+                    let rx = Arc::new(tokio::sync::Mutex::new(rx));
+                    let producers_done = Arc::new(AtomicBool::new(false));
+
+                    let mut producer_tasks = Vec::new();
+                    let mut consumer_tasks = Vec::new();
+
+                    for i in 0..num_producers {
+                        let tx = tx.clone();
+                        let metrics_clone = Arc::clone(&metrics);
+
+                        producer_tasks.push(tokio::spawn(async move {
+                            for j in 0..items_per_producer {
+                                let start_send = Instant::now();
+                                if tx.send(format!("Producer-{}-Item-{}", i, j)).is_ok() {
+                                    metrics_clone.increment_produced();
+                                    metrics_clone.record_channel_operation(start_send.elapsed());
+                                }
+
+                                if j % 100 == 0 {
+                                    tokio::time::sleep(Duration::from_micros(1)).await;
+                                }
+                            }
+                        }));
+                    }
+
+                    for _ in 0..num_consumers {
+                        let rx = Arc::clone(&rx);
+                        let metrics_clone = Arc::clone(&metrics);
+                        let producers_done_clone = Arc::clone(&producers_done);
+
+                        consumer_tasks.push(tokio::spawn(async move {
+                            loop {
+                                let start_recv = Instant::now();
+                                let received = {
+                                    let mut rx_guard = rx.lock().await;
+                                    match rx_guard.try_recv() {
+                                        Ok(_) => {
+                                            metrics_clone.record_channel_operation(start_recv.elapsed());
+                                            metrics_clone.increment_consumed();
+                                            true
+                                        }
+                                        Err(tokio::sync::mpsc::error::TryRecvError::Empty) => {
+                                            if producers_done_clone.load(Ordering::Relaxed) {
+                                                break;
+                                            }
+                                            false
+                                        }
+                                        Err(tokio::sync::mpsc::error::TryRecvError::Disconnected) => break,
+                                    }
+                                };
+
+                                if !received {
+                                    tokio::time::sleep(Duration::from_micros(10)).await;
+                                }
+                            }
+                        }));
+                    }
+
+                    drop(tx);
+
+                    for task in producer_tasks {
+                        task.await.unwrap();
+                    }
+
+                    producers_done.store(true, Ordering::Relaxed);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+
+                    for task in consumer_tasks {
+                        task.await.unwrap();
+                    }
+                });
+            }
+            ProducerConsumerMode::UdpLoopback => {
+                let producers_done = Arc::new(AtomicBool::new(false));
+
+                let consumer_sockets: Vec<std::net::UdpSocket> = (0..num_consumers)
+                    .map(|_| {
+                        let socket = std::net::UdpSocket::bind("127.0.0.1:0").expect("failed to bind UDP consumer socket");
+                        socket.set_read_timeout(Some(Duration::from_millis(20))).expect("failed to set read timeout");
+                        socket
+                    })
+                    .collect();
+                let consumer_addrs: Vec<std::net::SocketAddr> = consumer_sockets
+                    .iter()
+                    .map(|s| s.local_addr().expect("failed to read local_addr"))
+                    .collect();
+
+                let data_structures = vec![
+                    ("ratio_test_udp_sockets", std::mem::size_of::<std::net::UdpSocket>() * num_consumers),
+                    ("estimated_messages", num_producers * items_per_producer * 32),
+                ];
+                metrics.update_memory(data_structures, num_producers + num_consumers);
+
+                let mut producer_handles = Vec::new();
+                let mut consumer_handles = Vec::new();
+
+                for i in 0..num_producers {
+                    let metrics_clone = Arc::clone(&metrics);
+                    let consumer_addrs = consumer_addrs.clone();
+
+                    let handle = thread::spawn(move || {
+                        let socket = std::net::UdpSocket::bind("127.0.0.1:0").expect("failed to bind UDP producer socket");
+                        for j in 0..items_per_producer {
+                            let payload = format!("Producer-{}-Item-{}", i, j);
+                            let target = consumer_addrs[(i + j) % consumer_addrs.len()];
+                            let start_send = Instant::now();
+                            if socket.send_to(payload.as_bytes(), target).is_ok() {
+                                metrics_clone.increment_produced();
+                                metrics_clone.record_channel_operation(start_send.elapsed());
+                            }
+
+                            if j % 100 == 0 {
+                                thread::sleep(Duration::from_micros(1));
+                            }
+                        }
+                    });
+                    producer_handles.push(handle);
+                }
+
+                for socket in consumer_sockets {
+                    let metrics_clone = Arc::clone(&metrics);
+                    let producers_done_clone = Arc::clone(&producers_done);
+
+                    let handle = thread::spawn(move || {
+                        let mut buf = [0u8; 256];
+                        loop {
+                            let start_recv = Instant::now();
+                            match socket.recv(&mut buf) {
+                                Ok(_len) => {
+                                    metrics_clone.record_channel_operation(start_recv.elapsed());
+                                    metrics_clone.increment_consumed();
+                                }
+                                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::TimedOut => {
+                                    if producers_done_clone.load(Ordering::Relaxed) {
+                                        break;
+                                    }
+                                }
+                                Err(_) => break,
+                            }
+                        }
+                    });
+                    consumer_handles.push(handle);
+                }
+
+                for handle in producer_handles {
+                    handle.join().unwrap();
+                }
+
+                producers_done.store(true, Ordering::Relaxed);
+                thread::sleep(Duration::from_millis(50));
+
                 for handle in consumer_handles {
                     handle.join().unwrap();
                 }
             }
         }
-        
+
         let final_data_structures = vec![("ratio_test_cleanup", 512)];
         metrics.update_memory(final_data_structures, 1);
         
@@ -1050,35 +1854,37 @@ fn producer_consumer_ratio_test(mode: ProducerConsumerMode, total_threads: usize
 }
 
 fn main() {
-    let config = parse_args();
-    
+    let mut config = parse_args();
+
     if config.help {
         print_usage();
         return;
     }
-    
+
     let system_cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
-    
+
     println!("{}", "=".repeat(80));
     println!("RUST CONCURRENCY MECHANISMS COMPREHENSIVE BENCHMARK");
     println!("WITH DETAILED MEMORY ANALYSIS");
     println!("{}", "=".repeat(80));
-    
+
+    let (total_memory_kb, sysinfo_cores) = system_overview();
+    config.hardware_scores = calibrate();
+
     println!("PLATFORM:");
     println!("  System: {}", get_os_info());
     println!("  Architecture: {}", get_cpu_architecture());
     println!("  Available cores: {}", system_cores);
-    
+    println!("  Logical cores (sysinfo): {}", sysinfo_cores);
+    println!("  Total physical memory: {} KB ({:.1} GB)", total_memory_kb, total_memory_kb as f64 / 1024.0 / 1024.0);
+
+    println!("\nHARDWARE CALIBRATION (baseline for normalizing throughput numbers):");
+    println!("  CPU score: {:.0} ops/sec", config.hardware_scores.cpu_ops_per_sec);
+    println!("  Memory bandwidth: {:.2} GiB/sec", config.hardware_scores.memory_bandwidth_gib_per_sec);
+    println!("  Allocation score: {:.0} allocs/sec", config.hardware_scores.allocation_ops_per_sec);
+
     println!("\nMEMORY ANALYSIS CAPABILITIES:");
-    if cfg!(target_os = "linux") {
-        println!("  RSS measurement: /proc/self/status (Linux)");
-    } else if cfg!(target_os = "macos") {
-        println!("  RSS measurement: ps command (macOS)");
-    } else if cfg!(target_os = "windows") {
-        println!("  RSS measurement: tasklist command (Windows)");
-    } else {
-        println!("  RSS measurement: Not available on this platform");
-    }
+    println!("  RSS measurement: sysinfo::System (unified, no per-sample subprocess)");
     println!("  Heap estimation: Per data structure analysis");
     println!("  Thread overhead: Stack + metadata calculation");
     println!("  Runtime overhead: Language runtime analysis");
@@ -1100,6 +1906,14 @@ fn main() {
                  config.max_threads / system_cores);
     }
     
+    let monitor = if config.run_system_monitor {
+        println!("\nSYSTEM MONITOR: recording a sample every {:?} in the background (RSS refreshed every {:?}, CPU load every {:?})",
+                 SystemMonitorService::POLL_INTERVAL, SystemMonitorService::RSS_SAMPLE_INTERVAL, SystemMonitorService::CPU_SAMPLE_INTERVAL);
+        Some(SystemMonitorService::start())
+    } else {
+        None
+    };
+
     let threads_per_test = config.max_threads.min(8);
     let producers_consumers = (threads_per_test / 2).max(1);
     
@@ -1118,7 +1932,7 @@ fn main() {
     }
     
     if config.run_producer_consumer {
-        producer_consumer_benchmark(config.producer_consumer_mode, producers_consumers, producers_consumers, config.items_per_test);
+        producer_consumer_benchmark(config.producer_consumer_mode, producers_consumers, producers_consumers, config.items_per_test, threads_per_test);
     }
     
     if config.run_mutex_benchmark {
@@ -1126,17 +1940,22 @@ fn main() {
     }
     
     if config.run_csv_output {
-        benchmark_csv_output(config.csv_threads, config.csv_items);
+        benchmark_csv_output(config.csv_threads, config.csv_items, config.hardware_scores);
     }
     
     if config.run_producer_consumer && config.run_producer_consumer_ratio_test {
         producer_consumer_ratio_test(
             config.producer_consumer_mode,
             config.max_threads.min(16),
-            config.items_per_test / 2
+            config.items_per_test / 2,
+            &config.ratios.producer_percentages
         );
     }
     
+    if let Some(monitor) = monitor {
+        monitor.shutdown_and_dump(&config.system_monitor_output);
+    }
+
     println!("\n{}", "=".repeat(80));
     println!("RUST BENCHMARK COMPLETED WITH MEMORY ANALYSIS");
     println!("{}", "=".repeat(80));